@@ -0,0 +1,66 @@
+use alloc::{string::String, vec::Vec};
+
+/// The Raspberry Pi board revision code, as found in the `Revision` line of `/proc/cpuinfo` or the `system/linux,revision` device tree property.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RaspberryPiRevision {
+    /// The raw revision code, such as `0xc03114`
+    pub code: u32,
+    /// The model, such as `4 Model B` or `Zero 2 W`, if known
+    pub model: Option<&'static str>,
+    /// The board revision, such as `(1, 4)` for revision 1.4
+    pub revision: (u8, u8),
+    /// The installed memory, in MiB
+    pub memory_mb: Option<u32>,
+    /// The SoC, such as `BCM2711`, if known
+    pub processor: Option<&'static str>,
+    /// The manufacturer, such as `Sony UK`, if known
+    pub manufacturer: Option<&'static str>,
+}
+
+impl RaspberryPiRevision {
+    /// Decodes a Raspberry Pi revision code, in either the old style (such as `0x000e`) or the new style (such as `0xc03114`).
+    pub fn from_code(code: u32) -> Self {
+        crate::imp::devicetree::decode_raspberry_pi_revision(code)
+    }
+}
+
+/// The identity of the board, as described by its device tree.
+///
+/// Most Arm and RISC-V systems have no SMBIOS tables (see [`crate::dmi()`]), and instead identify the board in the root node of the device tree.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Board {
+    /// The `model` property, such as `Raspberry Pi 4 Model B Rev 1.4`
+    pub model: Option<String>,
+    /// The `compatible` property, from the most specific (the board) to the least specific (usually the SoC), such as `raspberrypi,4-model-b` and `brcm,bcm2711`
+    pub compatible: Vec<String>,
+    /// The vendor of the board, such as `NVIDIA` or `StarFive`, if it is known
+    pub vendor: Option<&'static str>,
+    /// The name of the board, such as `Jetson AGX Orin` or `MacBook Air (M2, 2022)`, if it is known
+    pub name: Option<String>,
+    /// The revision code of a Raspberry Pi
+    pub raspberry_pi: Option<RaspberryPiRevision>,
+}
+
+impl Board {
+    /// Identifies the board from the `model` and `compatible` properties of the root node of a device tree,
+    /// and the revision code of a Raspberry Pi (which is ignored for other boards).
+    ///
+    /// `compatible` is the raw property, which is a list of NUL-terminated strings.
+    pub fn from_device_tree(model: Option<&str>, compatible: &[u8], revision: Option<u32>) -> Self {
+        crate::imp::devicetree::identify(model, compatible, revision)
+    }
+}
+
+/// Formats the board as its known name, or else its `model` property
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (&self.name, &self.model, self.compatible.first()) {
+            (Some(name), _, _) => f.write_str(name),
+            (None, Some(model), _) => f.write_str(model),
+            (None, None, Some(compatible)) => f.write_str(compatible),
+            (None, None, None) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,1588 @@
+use alloc::string::{String, ToString};
+
+bitflags::bitflags! {
+    /// The set of x86 instruction set extensions supported by the host processor.
+    ///
+    /// Each flag corresponds to exactly one feature understood by `rustc -C target-feature`. The name used by rustc can be obtained by [`CpuFeatures::target_feature_names`].
+    ///
+    /// Features which require operating system support to save additional register state (AVX, AVX-512, AMX, and APX) are only reported if the OS has enabled that state.
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+    pub struct CpuFeatures : u128 {
+        /// `adx`
+        const ADX = 1 << 0;
+        /// `aes`
+        const AES = 1 << 1;
+        /// `amx-avx512`
+        const AMX_AVX512 = 1 << 2;
+        /// `amx-bf16`
+        const AMX_BF16 = 1 << 3;
+        /// `amx-complex`
+        const AMX_COMPLEX = 1 << 4;
+        /// `amx-fp16`
+        const AMX_FP16 = 1 << 5;
+        /// `amx-fp8`
+        const AMX_FP8 = 1 << 6;
+        /// `amx-int8`
+        const AMX_INT8 = 1 << 7;
+        /// `amx-movrs`
+        const AMX_MOVRS = 1 << 8;
+        /// `amx-tf32`
+        const AMX_TF32 = 1 << 9;
+        /// `amx-tile`
+        const AMX_TILE = 1 << 10;
+        /// `apxf`
+        const APXF = 1 << 11;
+        /// `avx`
+        const AVX = 1 << 12;
+        /// `avx10.1`
+        const AVX10_1 = 1 << 13;
+        /// `avx10.2`
+        const AVX10_2 = 1 << 14;
+        /// `avx2`
+        const AVX2 = 1 << 15;
+        /// `avx512bf16`
+        const AVX512BF16 = 1 << 16;
+        /// `avx512bitalg`
+        const AVX512BITALG = 1 << 17;
+        /// `avx512bw`
+        const AVX512BW = 1 << 18;
+        /// `avx512cd`
+        const AVX512CD = 1 << 19;
+        /// `avx512dq`
+        const AVX512DQ = 1 << 20;
+        /// `avx512f`
+        const AVX512F = 1 << 21;
+        /// `avx512fp16`
+        const AVX512FP16 = 1 << 22;
+        /// `avx512ifma`
+        const AVX512IFMA = 1 << 23;
+        /// `avx512vbmi`
+        const AVX512VBMI = 1 << 24;
+        /// `avx512vbmi2`
+        const AVX512VBMI2 = 1 << 25;
+        /// `avx512vl`
+        const AVX512VL = 1 << 26;
+        /// `avx512vnni`
+        const AVX512VNNI = 1 << 27;
+        /// `avx512vp2intersect`
+        const AVX512VP2INTERSECT = 1 << 28;
+        /// `avx512vpopcntdq`
+        const AVX512VPOPCNTDQ = 1 << 29;
+        /// `avxifma`
+        const AVXIFMA = 1 << 30;
+        /// `avxneconvert`
+        const AVXNECONVERT = 1 << 31;
+        /// `avxvnni`
+        const AVXVNNI = 1 << 32;
+        /// `avxvnniint16`
+        const AVXVNNIINT16 = 1 << 33;
+        /// `avxvnniint8`
+        const AVXVNNIINT8 = 1 << 34;
+        /// `bmi1`
+        const BMI1 = 1 << 35;
+        /// `bmi2`
+        const BMI2 = 1 << 36;
+        /// `cmpxchg16b`
+        const CMPXCHG16B = 1 << 37;
+        /// `ermsb`
+        const ERMSB = 1 << 38;
+        /// `f16c`
+        const F16C = 1 << 39;
+        /// `fma`
+        const FMA = 1 << 40;
+        /// `fxsr`
+        const FXSR = 1 << 41;
+        /// `gfni`
+        const GFNI = 1 << 42;
+        /// `kl`
+        const KL = 1 << 43;
+        /// `lahfsahf`
+        const LAHFSAHF = 1 << 44;
+        /// `lzcnt`
+        const LZCNT = 1 << 45;
+        /// `movbe`
+        const MOVBE = 1 << 46;
+        /// `movrs`
+        const MOVRS = 1 << 47;
+        /// `pclmulqdq`
+        const PCLMULQDQ = 1 << 48;
+        /// `popcnt`
+        const POPCNT = 1 << 49;
+        /// `prfchw`
+        const PRFCHW = 1 << 50;
+        /// `rdrand`
+        const RDRAND = 1 << 51;
+        /// `rdseed`
+        const RDSEED = 1 << 52;
+        /// `rtm`
+        const RTM = 1 << 53;
+        /// `sha`
+        const SHA = 1 << 54;
+        /// `sha512`
+        const SHA512 = 1 << 55;
+        /// `sm3`
+        const SM3 = 1 << 56;
+        /// `sm4`
+        const SM4 = 1 << 57;
+        /// `sse`
+        const SSE = 1 << 58;
+        /// `sse2`
+        const SSE2 = 1 << 59;
+        /// `sse3`
+        const SSE3 = 1 << 60;
+        /// `sse4.1`
+        const SSE4_1 = 1 << 61;
+        /// `sse4.2`
+        const SSE4_2 = 1 << 62;
+        /// `sse4a`
+        const SSE4A = 1 << 63;
+        /// `ssse3`
+        const SSSE3 = 1 << 64;
+        /// `tbm`
+        const TBM = 1 << 65;
+        /// `vaes`
+        const VAES = 1 << 66;
+        /// `vpclmulqdq`
+        const VPCLMULQDQ = 1 << 67;
+        /// `widekl`
+        const WIDEKL = 1 << 68;
+        /// `x87`
+        const X87 = 1 << 69;
+        /// `xop`
+        const XOP = 1 << 70;
+        /// `xsave`
+        const XSAVE = 1 << 71;
+        /// `xsavec`
+        const XSAVEC = 1 << 72;
+        /// `xsaveopt`
+        const XSAVEOPT = 1 << 73;
+        /// `xsaves`
+        const XSAVES = 1 << 74;
+    }
+}
+
+/// Returns the name of each flag in `table` that is set in `features`
+fn target_feature_names<F: bitflags::Flags + Copy>(
+    features: F,
+    table: &'static [(F, &'static str)],
+) -> impl Iterator<Item = &'static str> {
+    table
+        .iter()
+        .filter(move |(flag, _)| features.contains(*flag))
+        .map(|&(_, name)| name)
+}
+
+/// Formats feature names as a list suitable for `-C target-feature`
+fn to_target_feature_string<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let mut st = String::new();
+    let mut sep = "";
+    for name in names {
+        st.push_str(sep);
+        st.push('+');
+        st.push_str(name);
+        sep = ",";
+    }
+    st
+}
+
+const X86_TARGET_FEATURES: &[(CpuFeatures, &str)] = &[
+    (CpuFeatures::ADX, "adx"),
+    (CpuFeatures::AES, "aes"),
+    (CpuFeatures::AMX_AVX512, "amx-avx512"),
+    (CpuFeatures::AMX_BF16, "amx-bf16"),
+    (CpuFeatures::AMX_COMPLEX, "amx-complex"),
+    (CpuFeatures::AMX_FP16, "amx-fp16"),
+    (CpuFeatures::AMX_FP8, "amx-fp8"),
+    (CpuFeatures::AMX_INT8, "amx-int8"),
+    (CpuFeatures::AMX_MOVRS, "amx-movrs"),
+    (CpuFeatures::AMX_TF32, "amx-tf32"),
+    (CpuFeatures::AMX_TILE, "amx-tile"),
+    (CpuFeatures::APXF, "apxf"),
+    (CpuFeatures::AVX, "avx"),
+    (CpuFeatures::AVX10_1, "avx10.1"),
+    (CpuFeatures::AVX10_2, "avx10.2"),
+    (CpuFeatures::AVX2, "avx2"),
+    (CpuFeatures::AVX512BF16, "avx512bf16"),
+    (CpuFeatures::AVX512BITALG, "avx512bitalg"),
+    (CpuFeatures::AVX512BW, "avx512bw"),
+    (CpuFeatures::AVX512CD, "avx512cd"),
+    (CpuFeatures::AVX512DQ, "avx512dq"),
+    (CpuFeatures::AVX512F, "avx512f"),
+    (CpuFeatures::AVX512FP16, "avx512fp16"),
+    (CpuFeatures::AVX512IFMA, "avx512ifma"),
+    (CpuFeatures::AVX512VBMI, "avx512vbmi"),
+    (CpuFeatures::AVX512VBMI2, "avx512vbmi2"),
+    (CpuFeatures::AVX512VL, "avx512vl"),
+    (CpuFeatures::AVX512VNNI, "avx512vnni"),
+    (CpuFeatures::AVX512VP2INTERSECT, "avx512vp2intersect"),
+    (CpuFeatures::AVX512VPOPCNTDQ, "avx512vpopcntdq"),
+    (CpuFeatures::AVXIFMA, "avxifma"),
+    (CpuFeatures::AVXNECONVERT, "avxneconvert"),
+    (CpuFeatures::AVXVNNI, "avxvnni"),
+    (CpuFeatures::AVXVNNIINT16, "avxvnniint16"),
+    (CpuFeatures::AVXVNNIINT8, "avxvnniint8"),
+    (CpuFeatures::BMI1, "bmi1"),
+    (CpuFeatures::BMI2, "bmi2"),
+    (CpuFeatures::CMPXCHG16B, "cmpxchg16b"),
+    (CpuFeatures::ERMSB, "ermsb"),
+    (CpuFeatures::F16C, "f16c"),
+    (CpuFeatures::FMA, "fma"),
+    (CpuFeatures::FXSR, "fxsr"),
+    (CpuFeatures::GFNI, "gfni"),
+    (CpuFeatures::KL, "kl"),
+    (CpuFeatures::LAHFSAHF, "lahfsahf"),
+    (CpuFeatures::LZCNT, "lzcnt"),
+    (CpuFeatures::MOVBE, "movbe"),
+    (CpuFeatures::MOVRS, "movrs"),
+    (CpuFeatures::PCLMULQDQ, "pclmulqdq"),
+    (CpuFeatures::POPCNT, "popcnt"),
+    (CpuFeatures::PRFCHW, "prfchw"),
+    (CpuFeatures::RDRAND, "rdrand"),
+    (CpuFeatures::RDSEED, "rdseed"),
+    (CpuFeatures::RTM, "rtm"),
+    (CpuFeatures::SHA, "sha"),
+    (CpuFeatures::SHA512, "sha512"),
+    (CpuFeatures::SM3, "sm3"),
+    (CpuFeatures::SM4, "sm4"),
+    (CpuFeatures::SSE, "sse"),
+    (CpuFeatures::SSE2, "sse2"),
+    (CpuFeatures::SSE3, "sse3"),
+    (CpuFeatures::SSE4_1, "sse4.1"),
+    (CpuFeatures::SSE4_2, "sse4.2"),
+    (CpuFeatures::SSE4A, "sse4a"),
+    (CpuFeatures::SSSE3, "ssse3"),
+    (CpuFeatures::TBM, "tbm"),
+    (CpuFeatures::VAES, "vaes"),
+    (CpuFeatures::VPCLMULQDQ, "vpclmulqdq"),
+    (CpuFeatures::WIDEKL, "widekl"),
+    (CpuFeatures::X87, "x87"),
+    (CpuFeatures::XOP, "xop"),
+    (CpuFeatures::XSAVE, "xsave"),
+    (CpuFeatures::XSAVEC, "xsavec"),
+    (CpuFeatures::XSAVEOPT, "xsaveopt"),
+    (CpuFeatures::XSAVES, "xsaves"),
+];
+
+impl CpuFeatures {
+    /// Returns the names of each feature in `self`, as spelled by `rustc --print target-features`, in alphabetical order.
+    pub fn target_feature_names(self) -> impl Iterator<Item = &'static str> {
+        target_feature_names(self, X86_TARGET_FEATURES)
+    }
+
+    /// Formats `self` as a list suitable for `-C target-feature`, such as `+avx,+avx2,+fma`.
+    pub fn to_target_feature_string(self) -> String {
+        to_target_feature_string(self.target_feature_names())
+    }
+}
+
+/// Determines the x86 instruction set extensions supported by the host processor.
+///
+/// This is decoded from cpuid leaves 0x01, 0x07 (subleaves 0 and 1), 0x0D, 0x19, 0x1E, 0x24, and 0x8000_0001,
+/// and is masked by the register state the OS has enabled in `XCR0`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn x86_features() -> CpuFeatures {
+    crate::imp::x86::cpu_features()
+}
+
+bitflags::bitflags! {
+    /// The set of AArch64 architecture extensions supported by the host processor.
+    ///
+    /// Each flag corresponds to exactly one feature understood by `rustc -C target-feature`. The name used by rustc can be obtained by [`Aarch64Features::target_feature_names`].
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+    pub struct Aarch64Features : u64 {
+        /// `aes`
+        const AES = 1 << 0;
+        /// `bf16`
+        const BF16 = 1 << 1;
+        /// `bti`
+        const BTI = 1 << 2;
+        /// `crc`
+        const CRC = 1 << 3;
+        /// `cssc`
+        const CSSC = 1 << 4;
+        /// `dit`
+        const DIT = 1 << 5;
+        /// `dotprod`
+        const DOTPROD = 1 << 6;
+        /// `dpb`
+        const DPB = 1 << 7;
+        /// `dpb2`
+        const DPB2 = 1 << 8;
+        /// `ecv`
+        const ECV = 1 << 9;
+        /// `f32mm`
+        const F32MM = 1 << 10;
+        /// `f64mm`
+        const F64MM = 1 << 11;
+        /// `faminmax`
+        const FAMINMAX = 1 << 12;
+        /// `fcma`
+        const FCMA = 1 << 13;
+        /// `fhm`
+        const FHM = 1 << 14;
+        /// `flagm`
+        const FLAGM = 1 << 15;
+        /// `flagm2`
+        const FLAGM2 = 1 << 16;
+        /// `fp16`
+        const FP16 = 1 << 17;
+        /// `frintts`
+        const FRINTTS = 1 << 18;
+        /// `hbc`
+        const HBC = 1 << 19;
+        /// `i8mm`
+        const I8MM = 1 << 20;
+        /// `jsconv`
+        const JSCONV = 1 << 21;
+        /// `lse`
+        const LSE = 1 << 22;
+        /// `lse128`
+        const LSE128 = 1 << 23;
+        /// `lse2`
+        const LSE2 = 1 << 24;
+        /// `lut`
+        const LUT = 1 << 25;
+        /// `mops`
+        const MOPS = 1 << 26;
+        /// `mte`
+        const MTE = 1 << 27;
+        /// `neon`
+        const NEON = 1 << 28;
+        /// `paca`
+        const PACA = 1 << 29;
+        /// `pacg`
+        const PACG = 1 << 30;
+        /// `rand`
+        const RAND = 1 << 31;
+        /// `rcpc`
+        const RCPC = 1 << 32;
+        /// `rcpc2`
+        const RCPC2 = 1 << 33;
+        /// `rcpc3`
+        const RCPC3 = 1 << 34;
+        /// `rdm`
+        const RDM = 1 << 35;
+        /// `sb`
+        const SB = 1 << 36;
+        /// `sha2`
+        const SHA2 = 1 << 37;
+        /// `sha3`
+        const SHA3 = 1 << 38;
+        /// `sm4`
+        const SM4 = 1 << 39;
+        /// `sme`
+        const SME = 1 << 40;
+        /// `sme2`
+        const SME2 = 1 << 41;
+        /// `ssbs`
+        const SSBS = 1 << 42;
+        /// `sve`
+        const SVE = 1 << 43;
+        /// `sve2`
+        const SVE2 = 1 << 44;
+        /// `sve2-aes`
+        const SVE2_AES = 1 << 45;
+        /// `sve2-bitperm`
+        const SVE2_BITPERM = 1 << 46;
+        /// `sve2-sha3`
+        const SVE2_SHA3 = 1 << 47;
+        /// `sve2-sm4`
+        const SVE2_SM4 = 1 << 48;
+        /// `sve2p1`
+        const SVE2P1 = 1 << 49;
+        /// `wfxt`
+        const WFXT = 1 << 50;
+    }
+}
+
+const AARCH64_TARGET_FEATURES: &[(Aarch64Features, &str)] = &[
+    (Aarch64Features::AES, "aes"),
+    (Aarch64Features::BF16, "bf16"),
+    (Aarch64Features::BTI, "bti"),
+    (Aarch64Features::CRC, "crc"),
+    (Aarch64Features::CSSC, "cssc"),
+    (Aarch64Features::DIT, "dit"),
+    (Aarch64Features::DOTPROD, "dotprod"),
+    (Aarch64Features::DPB, "dpb"),
+    (Aarch64Features::DPB2, "dpb2"),
+    (Aarch64Features::ECV, "ecv"),
+    (Aarch64Features::F32MM, "f32mm"),
+    (Aarch64Features::F64MM, "f64mm"),
+    (Aarch64Features::FAMINMAX, "faminmax"),
+    (Aarch64Features::FCMA, "fcma"),
+    (Aarch64Features::FHM, "fhm"),
+    (Aarch64Features::FLAGM, "flagm"),
+    (Aarch64Features::FLAGM2, "flagm2"),
+    (Aarch64Features::FP16, "fp16"),
+    (Aarch64Features::FRINTTS, "frintts"),
+    (Aarch64Features::HBC, "hbc"),
+    (Aarch64Features::I8MM, "i8mm"),
+    (Aarch64Features::JSCONV, "jsconv"),
+    (Aarch64Features::LSE, "lse"),
+    (Aarch64Features::LSE128, "lse128"),
+    (Aarch64Features::LSE2, "lse2"),
+    (Aarch64Features::LUT, "lut"),
+    (Aarch64Features::MOPS, "mops"),
+    (Aarch64Features::MTE, "mte"),
+    (Aarch64Features::NEON, "neon"),
+    (Aarch64Features::PACA, "paca"),
+    (Aarch64Features::PACG, "pacg"),
+    (Aarch64Features::RAND, "rand"),
+    (Aarch64Features::RCPC, "rcpc"),
+    (Aarch64Features::RCPC2, "rcpc2"),
+    (Aarch64Features::RCPC3, "rcpc3"),
+    (Aarch64Features::RDM, "rdm"),
+    (Aarch64Features::SB, "sb"),
+    (Aarch64Features::SHA2, "sha2"),
+    (Aarch64Features::SHA3, "sha3"),
+    (Aarch64Features::SM4, "sm4"),
+    (Aarch64Features::SME, "sme"),
+    (Aarch64Features::SME2, "sme2"),
+    (Aarch64Features::SSBS, "ssbs"),
+    (Aarch64Features::SVE, "sve"),
+    (Aarch64Features::SVE2, "sve2"),
+    (Aarch64Features::SVE2_AES, "sve2-aes"),
+    (Aarch64Features::SVE2_BITPERM, "sve2-bitperm"),
+    (Aarch64Features::SVE2_SHA3, "sve2-sha3"),
+    (Aarch64Features::SVE2_SM4, "sve2-sm4"),
+    (Aarch64Features::SVE2P1, "sve2p1"),
+    (Aarch64Features::WFXT, "wfxt"),
+];
+
+// The features that each Armv8.x extension makes mandatory (and that Linux reports), starting from Armv8.1.
+// Pointer authentication and BTI are left out, since they are commonly hidden by the kernel or hypervisor
+const AARCH64_LEVELS: &[Aarch64Features] = &[
+    Aarch64Features::LSE
+        .union(Aarch64Features::RDM)
+        .union(Aarch64Features::CRC),
+    Aarch64Features::DPB,
+    Aarch64Features::JSCONV
+        .union(Aarch64Features::FCMA)
+        .union(Aarch64Features::RCPC),
+    Aarch64Features::DIT
+        .union(Aarch64Features::FLAGM)
+        .union(Aarch64Features::RCPC2)
+        .union(Aarch64Features::LSE2),
+    Aarch64Features::SB
+        .union(Aarch64Features::FRINTTS)
+        .union(Aarch64Features::FLAGM2)
+        .union(Aarch64Features::DPB2),
+    Aarch64Features::BF16.union(Aarch64Features::I8MM),
+    Aarch64Features::WFXT,
+    Aarch64Features::MOPS.union(Aarch64Features::HBC),
+    Aarch64Features::CSSC,
+];
+
+const ARMV8_NAMES: &[&str] = &[
+    "armv8-a",
+    "armv8.1-a",
+    "armv8.2-a",
+    "armv8.3-a",
+    "armv8.4-a",
+    "armv8.5-a",
+    "armv8.6-a",
+    "armv8.7-a",
+    "armv8.8-a",
+    "armv8.9-a",
+];
+
+const ARMV9_NAMES: &[&str] = &[
+    "armv9-a",
+    "armv9.1-a",
+    "armv9.2-a",
+    "armv9.3-a",
+    "armv9.4-a",
+];
+
+impl Aarch64Features {
+    /// Decodes the `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries reported by Linux on AArch64.
+    pub fn from_hwcap(hwcap: u64, hwcap2: u64) -> Self {
+        crate::imp::arm::features_from_hwcap(hwcap, hwcap2)
+    }
+
+    /// Returns the names of each feature in `self`, as spelled by `rustc --print target-features`, in alphabetical order.
+    pub fn target_feature_names(self) -> impl Iterator<Item = &'static str> {
+        target_feature_names(self, AARCH64_TARGET_FEATURES)
+    }
+
+    /// Formats `self` as a list suitable for `-C target-feature`, such as `+lse,+rcpc,+sve2`.
+    pub fn to_target_feature_string(self) -> String {
+        to_target_feature_string(self.target_feature_names())
+    }
+
+    /// Returns the most recent architecture version whose mandatory features are all in `self`, as accepted by `-march`, such as `armv8.2-a` or `armv9-a`.
+    ///
+    /// Armv9.x is reported when the features of the corresponding Armv8.(x+5) and SVE2 are all present.
+    pub fn architecture(self) -> &'static str {
+        let minor = AARCH64_LEVELS
+            .iter()
+            .take_while(|level| self.contains(**level))
+            .count();
+
+        if minor >= 5 && self.contains(Aarch64Features::SVE2) {
+            ARMV9_NAMES[minor - 5]
+        } else {
+            ARMV8_NAMES[minor]
+        }
+    }
+}
+
+/// Determines the AArch64 architecture extensions supported by the host processor.
+///
+/// On Linux, this is decoded from the `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries. Elsewhere, no features are reported.
+#[cfg(target_arch = "aarch64")]
+pub fn aarch64_features() -> Aarch64Features {
+    crate::imp::aarch64_features().unwrap_or_default()
+}
+
+bitflags::bitflags! {
+    /// The set of 32-bit Arm architecture extensions supported by the host processor.
+    ///
+    /// Each flag corresponds to exactly one feature understood by `rustc -C target-feature`. The name used by rustc can be obtained by [`ArmFeatures::target_feature_names`].
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+    pub struct ArmFeatures : u32 {
+        /// `aes`
+        const AES = 1 << 0;
+        /// `crc`
+        const CRC = 1 << 1;
+        /// `d32`
+        const D32 = 1 << 2;
+        /// `dotprod`
+        const DOTPROD = 1 << 3;
+        /// `dsp`
+        const DSP = 1 << 4;
+        /// `i8mm`
+        const I8MM = 1 << 5;
+        /// `neon`
+        const NEON = 1 << 6;
+        /// `sha2`
+        const SHA2 = 1 << 7;
+        /// `thumb2`
+        const THUMB2 = 1 << 8;
+        /// `v5te`
+        const V5TE = 1 << 9;
+        /// `v6`
+        const V6 = 1 << 10;
+        /// `v6t2`
+        const V6T2 = 1 << 11;
+        /// `v7`
+        const V7 = 1 << 12;
+        /// `v8`
+        const V8 = 1 << 13;
+        /// `vfp2`
+        const VFP2 = 1 << 14;
+        /// `vfp3`
+        const VFP3 = 1 << 15;
+        /// `vfp4`
+        const VFP4 = 1 << 16;
+    }
+}
+
+const ARM_TARGET_FEATURES: &[(ArmFeatures, &str)] = &[
+    (ArmFeatures::AES, "aes"),
+    (ArmFeatures::CRC, "crc"),
+    (ArmFeatures::D32, "d32"),
+    (ArmFeatures::DOTPROD, "dotprod"),
+    (ArmFeatures::DSP, "dsp"),
+    (ArmFeatures::I8MM, "i8mm"),
+    (ArmFeatures::NEON, "neon"),
+    (ArmFeatures::SHA2, "sha2"),
+    (ArmFeatures::THUMB2, "thumb2"),
+    (ArmFeatures::V5TE, "v5te"),
+    (ArmFeatures::V6, "v6"),
+    (ArmFeatures::V6T2, "v6t2"),
+    (ArmFeatures::V7, "v7"),
+    (ArmFeatures::V8, "v8"),
+    (ArmFeatures::VFP2, "vfp2"),
+    (ArmFeatures::VFP3, "vfp3"),
+    (ArmFeatures::VFP4, "vfp4"),
+];
+
+impl ArmFeatures {
+    /// Decodes the `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries reported by Linux on 32-bit Arm,
+    /// together with the architecture `version` (such as `7` for Armv7), which determines the `v*` and `thumb2` features.
+    pub fn from_hwcap(version: u8, hwcap: u32, hwcap2: u32) -> Self {
+        crate::imp::arm::arm_features_from_hwcap(version, hwcap, hwcap2)
+    }
+
+    /// Returns the names of each feature in `self`, as spelled by `rustc --print target-features`, in alphabetical order.
+    pub fn target_feature_names(self) -> impl Iterator<Item = &'static str> {
+        target_feature_names(self, ARM_TARGET_FEATURES)
+    }
+
+    /// Formats `self` as a list suitable for `-C target-feature`, such as `+neon,+thumb2,+v7,+vfp3`.
+    pub fn to_target_feature_string(self) -> String {
+        to_target_feature_string(self.target_feature_names())
+    }
+}
+
+/// The convention used by 32-bit Arm userspace to pass floating-point values between functions.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FloatAbi {
+    /// Floating-point values are passed in VFP registers (`gnueabihf`, Debian's `armhf`).
+    Hard,
+    /// Floating-point values are passed in integer registers (`gnueabi`, Debian's `armel`).
+    Soft,
+}
+
+impl FloatAbi {
+    /// Determines the float ABI of an Arm ELF file from its header (at least the first 40 bytes of the file),
+    /// or `None` if `header` is not the header of a 32-bit Arm ELF file.
+    ///
+    /// Files that use the old ABI, or an EABI version older than 5, are soft-float.
+    pub fn from_elf_header(header: &[u8]) -> Option<Self> {
+        crate::imp::arm::float_abi_from_elf_header(header)
+    }
+}
+
+/// Describes the host processor and userspace on 32-bit Arm.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ArmInfo {
+    /// The architecture version, such as `6` for Armv6 or `7` for Armv7.
+    ///
+    /// On 64-bit processors running 32-bit userspace, this is `8`.
+    pub version: u8,
+    /// The architecture extensions supported by the processor.
+    pub features: ArmFeatures,
+    /// The float ABI of the system's dynamic loader (or of the current executable, if it is statically linked), if it could be determined.
+    pub float_abi: Option<FloatAbi>,
+}
+
+/// Determines the architecture version, extensions, and float ABI of the host on 32-bit Arm.
+///
+/// # Implementation
+///
+/// On Linux, the version is read from `AT_PLATFORM` (such as `v7l`), and the extensions are decoded from `AT_HWCAP` and `AT_HWCAP2`.
+/// The float ABI is read from the ELF flags of the dynamic loader, which is found by `AT_BASE`, or of `/proc/self/exe` if there is no loader.
+///
+/// This distinguishes `armel`, `armhf`, and Raspbian's `armv6hf` userspace, regardless of the target the current program was compiled for.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the information is not available on the host OS.
+#[cfg(target_arch = "arm")]
+pub fn arm_info() -> Result<ArmInfo, crate::Error> {
+    crate::imp::arm_info().map_err(crate::Error::from_raw_os_error)
+}
+
+/// A RISC-V application profile, which requires a baseline set of extensions.
+///
+/// Profiles are ordered, so that a later profile compares greater than the profiles it extends.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum RiscvProfile {
+    /// RVA20U64
+    Rva20,
+    /// RVA22U64
+    Rva22,
+    /// RVA23U64
+    Rva23,
+}
+
+impl RiscvProfile {
+    /// Returns the name of the profile, such as `rva22u64`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RiscvProfile::Rva20 => "rva20u64",
+            RiscvProfile::Rva22 => "rva22u64",
+            RiscvProfile::Rva23 => "rva23u64",
+        }
+    }
+}
+
+impl core::fmt::Display for RiscvProfile {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The RISC-V base ISA and extensions supported by the host processor.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RiscvIsa {
+    /// The width of the integer registers, such as `64` for RV64.
+    pub xlen: u32,
+    /// The names of the supported extensions, in lowercase and in canonical order, such as `i`, `m`, `zba`, or `zicsr`.
+    pub extensions: alloc::vec::Vec<String>,
+}
+
+impl RiscvIsa {
+    /// Parses an ISA string, such as `rv64imafdc_zicsr_zifencei` or `RV64GC`.
+    ///
+    /// `g` is expanded to `imafd_zicsr_zifencei`, and version numbers (such as `2p1`) are discarded.
+    /// Returns `None` if `isa` does not start with `rv` and the register width.
+    pub fn parse(isa: &str) -> Option<Self> {
+        crate::imp::riscv::parse_isa_string(isa)
+    }
+
+    /// Parses the ISA string from the first `isa` line of the contents of a Linux `/proc/cpuinfo` file.
+    pub fn from_cpuinfo(cpuinfo: &str) -> Option<Self> {
+        crate::imp::riscv::isa_from_cpuinfo(cpuinfo)
+    }
+
+    /// Decodes the `RISCV_HWPROBE_KEY_BASE_BEHAVIOR` and `RISCV_HWPROBE_KEY_IMA_EXT_0` values returned by the Linux `riscv_hwprobe` system call.
+    ///
+    /// `riscv_hwprobe` does not report the register width, so [`RiscvIsa::xlen`] is that of the current program.
+    pub fn from_hwprobe(base_behavior: u64, ima_ext_0: u64) -> Self {
+        crate::imp::riscv::isa_from_hwprobe(base_behavior, ima_ext_0, None)
+    }
+
+    /// Returns whether the extension named `ext` (in lowercase, such as `v` or `zbb`) is supported.
+    pub fn contains(&self, ext: &str) -> bool {
+        self.extensions.iter().any(|e| e == ext)
+    }
+
+    /// Returns the most recent RVA profile whose required extensions are all supported, or `None` if not even RVA20U64 is.
+    ///
+    /// Only the extensions Linux is able to report are checked, so (for example) `zicntr` and `zicbop` are assumed.
+    pub fn profile(&self) -> Option<RiscvProfile> {
+        crate::imp::riscv::profile(self)
+    }
+}
+
+impl core::fmt::Display for RiscvIsa {
+    /// Formats the canonical ISA string, such as `rv64imafdc_zicsr_zifencei`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "rv{}", self.xlen)?;
+        for ext in &self.extensions {
+            if ext.len() > 1 {
+                f.write_str("_")?;
+            }
+            f.write_str(ext)?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps the RISC-V `mvendorid`, `marchid` and `mimpid` machine registers to the name LLVM uses for the processor,
+/// such as `sifive-u74` or `spacemit-x60`, or `None` if the processor is not known.
+pub fn riscv_processor_from_ids(mvendorid: u64, marchid: u64, mimpid: u64) -> Option<&'static str> {
+    crate::imp::riscv::processor_from_ids(mvendorid, marchid, mimpid)
+}
+
+/// Names the RISC-V processor described by the contents of a Linux `/proc/cpuinfo` file, as LLVM names it.
+///
+/// The `mvendorid`, `marchid` and `mimpid` lines are used if they identify a known processor.
+/// Otherwise, the `uarch` line (the device tree `compatible` string of the processor, such as `thead,c906`) is used.
+pub fn riscv_processor_from_cpuinfo(cpuinfo: &str) -> Option<&'static str> {
+    crate::imp::riscv::processor_from_cpuinfo(cpuinfo)
+}
+
+/// Determines the RISC-V ISA and extensions of the host processor.
+///
+/// # Implementation
+///
+/// On Linux, the `riscv_hwprobe` syscall reports the extensions supported by every processor, to which the extensions listed on the `isa` line of `/proc/cpuinfo` are added.
+/// On kernels without `riscv_hwprobe`, only `/proc/cpuinfo` is used.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if neither is available on the host OS.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub fn riscv_isa() -> Result<RiscvIsa, crate::Error> {
+    crate::imp::riscv_isa().map_err(crate::Error::from_raw_os_error)
+}
+
+/// The byte order of the host userspace.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Endianness {
+    /// Least significant byte first (such as `ppc64le`).
+    Little,
+    /// Most significant byte first (such as `ppc64`).
+    Big,
+}
+
+/// The version of the 64-bit PowerPC ELF ABI used by the host userspace.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PowerAbi {
+    /// The original ABI, with function descriptors, used by most big-endian distributions.
+    ElfV1,
+    /// The revised ABI, used by all little-endian distributions (and some big-endian ones, such as those using musl).
+    ElfV2,
+}
+
+/// Describes the host processor and userspace on PowerPC.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PowerInfo {
+    /// The name LLVM uses for the processor (such as `pwr9` or `pwr10`), if known.
+    ///
+    /// This follows the processor the kernel runs userspace as, which is older than the hardware in compatibility modes.
+    pub cpu: Option<&'static str>,
+    /// The platform the kernel runs userspace as (`AT_PLATFORM`), such as `power9`.
+    pub platform: String,
+    /// The platform of the hardware (`AT_BASE_PLATFORM`), if the kernel reports it. This differs from [`PowerInfo::platform`] in compatibility modes.
+    pub base_platform: Option<String>,
+    /// The latest version of the Power ISA supported, as `(major, minor)` (such as `(3, 1)` for ISA 3.1), if known.
+    pub isa: Option<(u8, u8)>,
+    /// The byte order of the host userspace.
+    pub endianness: Endianness,
+    /// The ELF ABI version of the host userspace on 64-bit PowerPC, or `None` on 32-bit PowerPC (or if it could not be determined).
+    pub abi: Option<PowerAbi>,
+}
+
+impl PowerInfo {
+    /// Decodes the `AT_PLATFORM`, `AT_BASE_PLATFORM`, `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries reported by Linux on PowerPC,
+    /// together with the ELF header of the dynamic loader (at least the first 52 bytes of the file), which determines the byte order and ABI of userspace.
+    ///
+    /// Without a `loader` header, the byte order is that of the current program, and the ABI is unknown.
+    pub fn from_auxv(
+        platform: &str,
+        base_platform: Option<&str>,
+        hwcap: u64,
+        hwcap2: u64,
+        loader: Option<&[u8]>,
+    ) -> Self {
+        crate::imp::powerpc::decode(platform, base_platform, hwcap, hwcap2, loader)
+    }
+
+    /// Returns the [`Uname::hardware_platform`][crate::Uname::hardware_platform] of a system with this processor and the given `machine`,
+    /// which is the machine followed by the Power ISA version (such as `ppc64le-isa3.1`), or just the machine if the ISA version is not known.
+    pub fn hardware_platform(&self, machine: &str) -> String {
+        match self.isa {
+            Some((major, minor)) => alloc::format!("{machine}-isa{major}.{minor}"),
+            None => machine.into(),
+        }
+    }
+}
+
+/// Determines the processor, Power ISA version, byte order, and ABI of the host on PowerPC.
+///
+/// # Implementation
+///
+/// On Linux, the processor is read from `AT_PLATFORM` and `AT_BASE_PLATFORM`, and the ISA version from the `arch_*` bits of `AT_HWCAP` and `AT_HWCAP2`.
+/// The byte order and ABI are read from the ELF header of the dynamic loader, which is found by `AT_BASE`, or of `/proc/self/exe` if there is no loader.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the information is not available on the host OS.
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+pub fn power_info() -> Result<PowerInfo, crate::Error> {
+    crate::imp::power_info().map_err(crate::Error::from_raw_os_error)
+}
+
+/// Describes the host processor on IBM Z (s390x).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct S390xInfo {
+    /// The machine type, such as `3931` for a z16, if known.
+    pub machine_type: Option<u16>,
+    /// The architecture level usable by userspace, such as `14` for `arch14` (z16), if known.
+    ///
+    /// This is lower than the level of the machine if the kernel does not enable the vector facilities that the machine's level requires.
+    pub arch_level: Option<u8>,
+}
+
+impl S390xInfo {
+    /// Decodes the contents of a Linux `/proc/cpuinfo` file, which contains the machine type, and the `AT_HWCAP` auxiliary vector entry, which reports the vector facilities (`vx`, `vxe`, `vxe2`, and `nnpa`) enabled by the kernel.
+    pub fn from_cpuinfo(cpuinfo: &str, hwcap: u64) -> Self {
+        crate::imp::s390x::decode(cpuinfo, hwcap)
+    }
+
+    /// Returns the architecture level implemented by the machine type `machine_type`, such as `14` for `3931` (z16), or `None` if the machine type is not known.
+    pub fn machine_arch_level(machine_type: u16) -> Option<u8> {
+        crate::imp::s390x::machine_arch_level(machine_type)
+    }
+
+    /// Returns the name of the processor, as accepted by `-march`, such as `z16`, or `None` if the architecture level is not known.
+    ///
+    /// Each architecture level `N` is also accepted as `archN`, such as `arch14`.
+    pub fn processor(&self) -> Option<&'static str> {
+        self.arch_level
+            .and_then(crate::imp::s390x::processor_from_level)
+    }
+}
+
+/// Determines the machine type and architecture level of the host on IBM Z.
+///
+/// # Implementation
+///
+/// On Linux, the machine type is read from `/proc/cpuinfo`, and the vector facilities enabled by the kernel from `AT_HWCAP`.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the information is not available on the host OS.
+#[cfg(target_arch = "s390x")]
+pub fn s390x_info() -> Result<S390xInfo, crate::Error> {
+    crate::imp::s390x_info().map_err(crate::Error::from_raw_os_error)
+}
+
+bitflags::bitflags! {
+    /// The set of LoongArch architecture extensions supported by the host processor.
+    ///
+    /// Each flag corresponds to exactly one feature understood by `rustc -C target-feature`. The name used by rustc can be obtained by [`LoongArchFeatures::target_feature_names`].
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+    pub struct LoongArchFeatures : u32 {
+        /// `d`
+        const D = 1 << 0;
+        /// `div32`
+        const DIV32 = 1 << 1;
+        /// `f`
+        const F = 1 << 2;
+        /// `frecipe`
+        const FRECIPE = 1 << 3;
+        /// `lam-bh`
+        const LAM_BH = 1 << 4;
+        /// `lamcas`
+        const LAMCAS = 1 << 5;
+        /// `lasx`
+        const LASX = 1 << 6;
+        /// `lbt`
+        const LBT = 1 << 7;
+        /// `ld-seq-sa`
+        const LD_SEQ_SA = 1 << 8;
+        /// `lsx`
+        const LSX = 1 << 9;
+        /// `lvz`
+        const LVZ = 1 << 10;
+        /// `scq`
+        const SCQ = 1 << 11;
+        /// `ual`
+        const UAL = 1 << 12;
+    }
+}
+
+const LOONGARCH_TARGET_FEATURES: &[(LoongArchFeatures, &str)] = &[
+    (LoongArchFeatures::D, "d"),
+    (LoongArchFeatures::DIV32, "div32"),
+    (LoongArchFeatures::F, "f"),
+    (LoongArchFeatures::FRECIPE, "frecipe"),
+    (LoongArchFeatures::LAM_BH, "lam-bh"),
+    (LoongArchFeatures::LAMCAS, "lamcas"),
+    (LoongArchFeatures::LASX, "lasx"),
+    (LoongArchFeatures::LBT, "lbt"),
+    (LoongArchFeatures::LD_SEQ_SA, "ld-seq-sa"),
+    (LoongArchFeatures::LSX, "lsx"),
+    (LoongArchFeatures::LVZ, "lvz"),
+    (LoongArchFeatures::SCQ, "scq"),
+    (LoongArchFeatures::UAL, "ual"),
+];
+
+impl LoongArchFeatures {
+    /// Returns the names of each feature in `self`, as spelled by `rustc --print target-features`, in alphabetical order.
+    pub fn target_feature_names(self) -> impl Iterator<Item = &'static str> {
+        target_feature_names(self, LOONGARCH_TARGET_FEATURES)
+    }
+
+    /// Formats `self` as a list suitable for `-C target-feature`, such as `+lasx,+lsx,+ual`.
+    pub fn to_target_feature_string(self) -> String {
+        to_target_feature_string(self.target_feature_names())
+    }
+}
+
+/// Describes the host processor on LoongArch.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoongArchInfo {
+    /// The processor identifier (`CPUCFG` word 0), if known.
+    pub prid: Option<u32>,
+    /// The width of the general-purpose registers, `32` or `64`.
+    pub grlen: u32,
+    /// The architecture extensions supported by the processor.
+    pub features: LoongArchFeatures,
+}
+
+impl LoongArchInfo {
+    /// Decodes `CPUCFG` words 0 to 3, as returned by the `cpucfg` instruction.
+    pub fn from_cpucfg(words: [u32; 4]) -> Self {
+        crate::imp::loongarch::decode_cpucfg(words)
+    }
+
+    /// Decodes the `PRID`, `ISA` and `Features` lines of the contents of a Linux `/proc/cpuinfo` file.
+    ///
+    /// Linux does not report every extension in `/proc/cpuinfo`, so this may report an older architecture version than [`LoongArchInfo::from_cpucfg`].
+    pub fn from_cpuinfo(cpuinfo: &str) -> Self {
+        crate::imp::loongarch::decode_cpuinfo(cpuinfo)
+    }
+
+    /// Returns the name of the processor core, as accepted by `-march`, such as `la464` or `la664`, or `None` if the processor is not known.
+    pub fn processor(&self) -> Option<&'static str> {
+        self.prid
+            .and_then(crate::imp::loongarch::processor_from_prid)
+    }
+
+    /// Returns the architecture version whose required extensions are all supported, as accepted by `-march`, such as `la64v1.0` or `la64v1.1`,
+    /// or `None` if not even `la64v1.0` is supported.
+    pub fn architecture(&self) -> Option<&'static str> {
+        crate::imp::loongarch::architecture(self)
+    }
+}
+
+/// Determines the processor and architecture extensions of the host on LoongArch, by executing the `cpucfg` instruction.
+#[cfg(target_arch = "loongarch64")]
+pub fn loongarch_info() -> LoongArchInfo {
+    crate::imp::loongarch::decode_cpucfg(crate::imp::loongarch::cpucfg())
+}
+
+/// Describes the host processor on MIPS, as reported by Linux in `/proc/cpuinfo`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MipsInfo {
+    /// The `cpu model` line, such as `MIPS 24Kc V7.4` or `Cavium Octeon III V0.2  FPU V0.0`.
+    pub model: String,
+    /// The name of the processor as accepted by `-march`, such as `24kc`, `octeon3`, or `loongson3a`, if known.
+    pub cpu: Option<&'static str>,
+    /// The newest ISA revision on the `isa` line, as accepted by `-march`, such as `mips32r2` or `mips64r6`.
+    pub isa: Option<&'static str>,
+    /// The names of the application-specific extensions on the `ASEs implemented` line, such as `dsp`, `msa`, or `vz`.
+    pub ases: alloc::vec::Vec<String>,
+}
+
+impl MipsInfo {
+    /// Parses the contents of a Linux `/proc/cpuinfo` file, or returns `None` if it has no `cpu model` line.
+    pub fn from_cpuinfo(cpuinfo: &str) -> Option<Self> {
+        crate::imp::mips::decode_cpuinfo(cpuinfo)
+    }
+}
+
+/// Determines the processor, ISA revision, and extensions of the host on MIPS.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the information is not available on the host OS.
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "mips32r6",
+    target_arch = "mips64r6"
+))]
+pub fn mips_info() -> Result<MipsInfo, crate::Error> {
+    crate::imp::mips_info().map_err(crate::Error::from_raw_os_error)
+}
+
+/// Describes the host processor on SPARC, as reported by Linux in `/proc/cpuinfo`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SparcInfo {
+    /// The `cpu` line, such as `UltraSparc T2 (Niagara2)`.
+    pub model: String,
+    /// The name of the processor as accepted by `-mcpu`, such as `niagara4` or `ultrasparc3`, if known.
+    pub cpu: Option<&'static str>,
+    /// The `type` line, which is the machine architecture, such as `sun4v` or `sun4u`.
+    pub machine_type: Option<String>,
+}
+
+impl SparcInfo {
+    /// Parses the contents of a Linux `/proc/cpuinfo` file, or returns `None` if it has no `cpu` line.
+    pub fn from_cpuinfo(cpuinfo: &str) -> Option<Self> {
+        crate::imp::sparc::decode_cpuinfo(cpuinfo)
+    }
+}
+
+/// Determines the processor and machine architecture of the host on SPARC.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the information is not available on the host OS.
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+pub fn sparc_info() -> Result<SparcInfo, crate::Error> {
+    crate::imp::sparc_info().map_err(crate::Error::from_raw_os_error)
+}
+
+/// Returns the features supported by the host processor as a list suitable for `-C target-feature`, such as `+avx,+avx2,+fma`.
+///
+/// Returns `None` if feature detection is not supported for the host architecture, or no features were detected.
+pub fn target_features() -> Option<String> {
+    let features: Option<String> = cfg_match::cfg_match! {
+        any(target_arch = "x86", target_arch = "x86_64") => Some(x86_features().to_target_feature_string()),
+        target_arch = "aarch64" => Some(aarch64_features().to_target_feature_string()),
+        target_arch = "loongarch64" => Some(loongarch_info().features.to_target_feature_string()),
+        target_arch = "arm" => arm_info().ok().map(|info| info.features.to_target_feature_string()),
+        _ => None,
+    };
+
+    features.filter(|features| !features.is_empty())
+}
+
+/// A compiler (or toolchain) that accepts a processor name to tune or select code generation.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// GCC's `-march` (or `-mcpu` on Arm, RISC-V, and SPARC)
+    Gcc,
+    /// Clang's `-march` (or `-mcpu` on Arm, RISC-V, and SPARC)
+    Clang,
+    /// rustc's `-C target-cpu`
+    Rustc,
+    /// MSVC's `/arch:`
+    Msvc,
+    /// Go's `GOAMD64` or `GO386` environment variables
+    Go,
+}
+
+/// How the [`ProcessorName`] was determined.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProcessorMatch {
+    /// The processor was identified exactly, such as by the family and model reported by cpuid.
+    Exact,
+    /// The processor was not known, and the closest known processor was inferred from the features it supports.
+    Inferred,
+    /// Nothing is known about the processor other than the architecture.
+    Generic,
+}
+
+/// The name of the host processor, which can be rendered for various compilers.
+///
+/// The name is spelled as LLVM spells it where LLVM knows the processor (and as GCC spells it otherwise), and is translated to the closest equivalent for each [`Dialect`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ProcessorName {
+    cpu: String,
+    kind: ProcessorMatch,
+    gcc: Option<String>,
+    llvm: Option<String>,
+    msvc: Option<&'static str>,
+    go: Option<(&'static str, String)>,
+}
+
+impl ProcessorName {
+    /// Returns the processor name, as spelled by LLVM (or GCC, if LLVM does not know the processor).
+    ///
+    /// This is the same as [`Uname::processor`][crate::Uname::processor]
+    pub fn as_str(&self) -> &str {
+        &self.cpu
+    }
+
+    /// Returns whether the processor was identified exactly, inferred from its features, or not identified at all.
+    pub fn match_kind(&self) -> ProcessorMatch {
+        self.kind
+    }
+
+    /// Returns the name of the processor as accepted by `dialect`, or `None` if `dialect` has no way of naming the processor.
+    ///
+    /// For [`Dialect::Msvc`] and [`Dialect::Go`], this is the closest architecture level supported by the host processor, such as `AVX2` or `v3`.
+    pub fn name(&self, dialect: Dialect) -> Option<&str> {
+        match dialect {
+            Dialect::Gcc => self.gcc.as_deref(),
+            Dialect::Clang | Dialect::Rustc => self.llvm.as_deref(),
+            Dialect::Msvc => self.msvc,
+            Dialect::Go => self.go.as_ref().map(|(_, level)| &**level),
+        }
+    }
+
+    /// Returns the complete compiler flag (or environment variable assignment for [`Dialect::Go`]) selecting the processor for `dialect`,
+    /// or `None` if `dialect` has no way of naming the processor.
+    pub fn flag(&self, dialect: Dialect) -> Option<String> {
+        let name = self.name(dialect)?;
+        Some(match dialect {
+            // Arm, RISC-V, and SPARC compilers take an architecture version or ISA string (if anything) in `-march`, and the processor in `-mcpu`
+            Dialect::Gcc | Dialect::Clang
+                if cfg!(any(
+                    target_arch = "aarch64",
+                    target_arch = "arm",
+                    target_arch = "riscv32",
+                    target_arch = "riscv64",
+                    target_arch = "sparc",
+                    target_arch = "sparc64"
+                )) =>
+            {
+                alloc::format!("-mcpu={name}")
+            }
+            Dialect::Gcc | Dialect::Clang => alloc::format!("-march={name}"),
+            Dialect::Rustc => alloc::format!("-C target-cpu={name}"),
+            Dialect::Msvc => alloc::format!("/arch:{name}"),
+            Dialect::Go => alloc::format!("{}={name}", self.go.as_ref()?.0),
+        })
+    }
+}
+
+impl core::fmt::Display for ProcessorName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.cpu)
+    }
+}
+
+// Processors known to LLVM, but not GCC, and the closest name GCC knows
+const LLVM_ONLY_PROCESSORS: &[(&str, &str)] = &[
+    ("yonah", "pentium-m"),
+    ("penryn", "core2"),
+    ("knl", "broadwell"),
+    ("knm", "broadwell"),
+    ("gracemont", "alderlake"),
+    ("pwr4", "power4"),
+    ("pwr5", "power5"),
+    ("pwr5x", "power5+"),
+    ("pwr6", "power6"),
+    ("pwr6x", "power6x"),
+    ("pwr7", "power7"),
+    ("pwr8", "power8"),
+    ("pwr9", "power9"),
+    ("pwr10", "power10"),
+    ("pwr11", "power11"),
+];
+
+// Processors known to GCC, but not LLVM, and the closest name LLVM knows
+const GCC_ONLY_PROCESSOR_EQUIVALENTS: &[(&str, &str)] = &[
+    ("4kc", "mips32"),
+    ("4km", "mips32"),
+    ("4kp", "mips32"),
+    ("4ksc", "mips32"),
+    ("4kec", "mips32r2"),
+    ("4kem", "mips32r2"),
+    ("4kep", "mips32r2"),
+    ("4ksd", "mips32r2"),
+    ("5kc", "mips64"),
+    ("5kf", "mips64"),
+    ("20kc", "mips64"),
+    ("24kc", "mips32r2"),
+    ("24kec", "mips32r2"),
+    ("34kc", "mips32r2"),
+    ("34kn", "mips32r2"),
+    ("74kc", "mips32r2"),
+    ("1004kc", "mips32r2"),
+    ("interaptiv", "mips32r2"),
+    ("m4k", "mips32r2"),
+    ("m14k", "mips32r2"),
+    ("m14kc", "mips32r2"),
+    ("m14ke", "mips32r2"),
+    ("m14kec", "mips32r2"),
+    ("m5100", "mips32r5"),
+    ("m5101", "mips32r5"),
+    ("i6400", "mips64r6"),
+    ("i6500", "mips64r6"),
+    ("p6600", "mips64r6"),
+    ("octeon2", "octeon+"),
+    ("octeon3", "octeon+"),
+    ("loongson2e", "mips3"),
+    ("loongson2f", "mips3"),
+    ("loongson3a", "mips64r2"),
+    ("gs464e", "mips64r2"),
+    ("niagara7", "niagara4"),
+    ("m8", "niagara4"),
+];
+
+// Processors known to GCC, but not LLVM. LLVM uses the generic name for the architecture level instead
+const GCC_ONLY_PROCESSORS: &[&str] = &[
+    "c7",
+    "nano",
+    "nano-3000",
+    "eden-x4",
+    "lujiazui",
+    "yongfeng",
+    "shijidadao",
+    "z900",
+    "z990",
+    "z9-ec",
+];
+
+/// Spells a known `processor` for GCC and for LLVM, where `generic` is the name of the architecture level, used if LLVM has no equivalent processor
+fn gcc_llvm_names(processor: &str, generic: Option<String>) -> (Option<String>, Option<String>) {
+    if let Some(&(_, gcc)) = LLVM_ONLY_PROCESSORS
+        .iter()
+        .find(|(llvm, _)| *llvm == processor)
+    {
+        (Some(gcc.to_string()), Some(processor.to_string()))
+    } else if let Some(&(_, llvm)) = GCC_ONLY_PROCESSOR_EQUIVALENTS
+        .iter()
+        .find(|(gcc, _)| *gcc == processor)
+    {
+        (Some(processor.to_string()), Some(llvm.to_string()))
+    } else if GCC_ONLY_PROCESSORS.contains(&processor) {
+        (Some(processor.to_string()), generic)
+    } else {
+        (Some(processor.to_string()), Some(processor.to_string()))
+    }
+}
+
+/// Maps the value of the Arm Main ID Register (`MIDR_EL1`, or `MIDR` on 32-bit Arm) to the name LLVM uses for the processor,
+/// such as `cortex-a72`, `neoverse-v2`, or `apple-m1`, or `None` if the implementer and part number are not known.
+///
+/// This is how [`Uname::processor`][crate::Uname::processor] is determined on Arm.
+pub fn processor_from_midr(midr: u32) -> Option<&'static str> {
+    crate::imp::arm::processor_from_midr(midr)
+}
+
+/// Reconstructs the Arm Main ID Register of each processor listed in the contents of a Linux `/proc/cpuinfo` file,
+/// from its `CPU implementer`, `CPU variant`, `CPU part` and `CPU revision` lines.
+pub fn midrs_from_cpuinfo(cpuinfo: &str) -> alloc::vec::Vec<u32> {
+    crate::imp::arm::midrs_from_cpuinfo(cpuinfo)
+}
+
+/// Determines the [`ProcessorName`] of the host processor.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if [`uname`][crate::uname] fails.
+pub fn processor_name() -> Result<ProcessorName, crate::Error> {
+    let uname = crate::uname()?;
+
+    let level = uname
+        .hardware_platform
+        .strip_prefix("x86_64-v")
+        .and_then(|v| v.parse::<u32>().ok());
+
+    // An unrecognized processor only has a generic name on x86, where it can be named by the architecture level
+    let generic = match (&*uname.machine, level) {
+        ("x86_64", None | Some(1)) => Some("x86-64".to_string()),
+        ("x86_64", Some(level)) => Some(alloc::format!("x86-64-v{level}")),
+        (mach @ ("i386" | "i486" | "i586" | "i686"), _) => Some(mach.to_string()),
+        _ => None,
+    };
+
+    let (gcc, llvm) = if uname.processor == uname.machine {
+        (generic.clone(), generic)
+    } else {
+        gcc_llvm_names(&uname.processor, generic)
+    };
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let kind = match crate::imp::x86::identify_processor() {
+        Some((_, kind)) => kind,
+        None => ProcessorMatch::Generic,
+    };
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    let kind = if uname.processor != uname.machine {
+        ProcessorMatch::Exact
+    } else {
+        ProcessorMatch::Generic
+    };
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let (msvc, go) = {
+        let features = x86_features();
+        let avx512 = CpuFeatures::AVX512F
+            | CpuFeatures::AVX512CD
+            | CpuFeatures::AVX512BW
+            | CpuFeatures::AVX512DQ
+            | CpuFeatures::AVX512VL;
+
+        let msvc = if features.contains(CpuFeatures::AVX10_2) {
+            Some("AVX10.2")
+        } else if features.contains(CpuFeatures::AVX10_1) {
+            Some("AVX10.1")
+        } else if features.contains(avx512) {
+            Some("AVX512")
+        } else if features.contains(CpuFeatures::AVX2) {
+            Some("AVX2")
+        } else if features.contains(CpuFeatures::AVX) {
+            Some("AVX")
+        } else if cfg!(target_arch = "x86_64") || features.contains(CpuFeatures::SSE2) {
+            Some("SSE2")
+        } else if features.contains(CpuFeatures::SSE) {
+            Some("SSE")
+        } else {
+            Some("IA32")
+        };
+
+        let go = if cfg!(target_arch = "x86_64") {
+            Some(("GOAMD64", alloc::format!("v{}", level.unwrap_or(1))))
+        } else if features.contains(CpuFeatures::SSE2) {
+            Some(("GO386", "sse2".to_string()))
+        } else {
+            Some(("GO386", "softfloat".to_string()))
+        };
+
+        (msvc, go)
+    };
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    let (msvc, go) = (None, None);
+
+    Ok(ProcessorName {
+        cpu: uname.processor,
+        kind,
+        gcc,
+        llvm,
+        msvc,
+        go,
+    })
+}
+
+/// The kind of data held by a [`Cache`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CacheType {
+    /// A cache that only holds data.
+    Data,
+    /// A cache that only holds instructions.
+    Instruction,
+    /// A cache that holds both data and instructions.
+    Unified,
+}
+
+/// Describes a single cache in the host processor's cache hierarchy.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Cache {
+    /// The level of the cache, starting from 1 for the cache closest to the core.
+    pub level: u8,
+    /// The kind of data held by the cache.
+    pub cache_type: CacheType,
+    /// The total size of the cache, in bytes.
+    pub size: u64,
+    /// The size of a cache line, in bytes.
+    pub line_size: u32,
+    /// The number of ways of associativity, or `None` if the cache is fully associative (or the associativity is not known).
+    pub associativity: Option<u32>,
+    /// The (maximum) number of logical processors that share the cache, or `0` if not known.
+    pub shared_by: u32,
+}
+
+/// Determines the cache hierarchy of the host processor, as seen by the current processor.
+///
+/// # Implementation
+///
+/// On x86, this uses cpuid leaf 0x04 on Intel processors, and leaf 0x8000_001D (or leaves 0x8000_0005 and 0x8000_0006 on older processors) on AMD processors.
+/// On other architectures, this reads `/sys/devices/system/cpu/cpu0/cache` on Linux.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the cache hierarchy cannot be determined on the host.
+pub fn caches() -> Result<alloc::vec::Vec<Cache>, crate::Error> {
+    crate::imp::caches().map_err(crate::Error::from_raw_os_error)
+}
+
+/// The kind of core that a logical processor belongs to, on processors that mix different kinds of cores.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoreType {
+    /// A performance core, such as an Intel P-core or an Arm "big" core.
+    ///
+    /// On processors that do not mix different kinds of cores, every core is a performance core.
+    Performance,
+    /// An efficiency core, such as an Intel E-core or an Arm "LITTLE" core.
+    Efficiency,
+    /// The kind of core could not be determined.
+    Unknown,
+}
+
+/// Describes a single online logical processor.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LogicalCpu {
+    /// The number the OS uses to identify the logical processor.
+    pub cpu: u32,
+    /// The kind of core the logical processor belongs to.
+    pub core_type: CoreType,
+    /// The name of the core's microarchitecture (such as `goldencove` or `gracemont`), if known.
+    pub core_name: Option<&'static str>,
+    /// The physical package (socket) the logical processor belongs to, if known.
+    pub package: Option<u32>,
+    /// The identifier of the core the logical processor belongs to within its package, if known.
+    pub core: Option<u32>,
+}
+
+/// The number of physical cores of each [`CoreType`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct CoreSummary {
+    /// The number of performance cores
+    pub performance: u32,
+    /// The number of efficiency cores
+    pub efficiency: u32,
+    /// The number of cores of an unknown type
+    pub unknown: u32,
+}
+
+impl CoreSummary {
+    /// Summarizes the core types of `cpus`, counting each physical core once, regardless of how many logical processors it has.
+    ///
+    /// Logical processors that do not know their core are each counted as a separate core.
+    pub fn from_cpus(cpus: &[LogicalCpu]) -> Self {
+        let mut summary = CoreSummary::default();
+        let mut seen = alloc::collections::BTreeSet::new();
+
+        for cpu in cpus {
+            if let (Some(package), Some(core)) = (cpu.package, cpu.core)
+                && !seen.insert((package, core))
+            {
+                continue;
+            }
+
+            match cpu.core_type {
+                CoreType::Performance => summary.performance += 1,
+                CoreType::Efficiency => summary.efficiency += 1,
+                CoreType::Unknown => summary.unknown += 1,
+            }
+        }
+
+        summary
+    }
+}
+
+/// Determines the online logical processors of the host, and the kind of core that each belongs to.
+///
+/// # Implementation
+///
+/// On Linux, this reads `/sys/devices/cpu_core/cpus` and `/sys/devices/cpu_atom/cpus`.
+/// If those are not available on a hybrid x86 processor, each logical processor is queried with cpuid leaf 0x1A.
+/// On other architectures, cores are classified by their `cpu_capacity` in sysfs.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the logical processors cannot be determined on the host.
+pub fn logical_cpus() -> Result<alloc::vec::Vec<LogicalCpu>, crate::Error> {
+    crate::imp::logical_cpus().map_err(crate::Error::from_raw_os_error)
+}
+
+/// Determines the number of physical cores of each [`CoreType`] on the host.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the logical processors cannot be determined on the host.
+pub fn core_summary() -> Result<CoreSummary, crate::Error> {
+    logical_cpus().map(|cpus| CoreSummary::from_cpus(&cpus))
+}
+
+/// Describes how the logical processors of the host are arranged into cores and packages (sockets).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Topology {
+    /// The number of packages (sockets) with at least one online logical processor.
+    pub sockets: u32,
+    /// The (maximum) number of online cores in each package.
+    pub cores_per_socket: u32,
+    /// The (maximum) number of online logical processors in each core.
+    pub threads_per_core: u32,
+    /// The logical processors that are currently online.
+    pub online: alloc::vec::Vec<u32>,
+    /// The logical processors that could be brought online, including those that are offline or not present.
+    pub possible: alloc::vec::Vec<u32>,
+}
+
+/// Determines the [`Topology`] of the host.
+///
+/// # Implementation
+///
+/// On Linux, this reads `/sys/devices/system/cpu`.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the topology cannot be determined on the host.
+pub fn topology() -> Result<Topology, crate::Error> {
+    crate::imp::topology().map_err(crate::Error::from_raw_os_error)
+}
+
+/// Determines the number of threads the current process can effectively run in parallel.
+///
+/// This is never more than the number of online logical processors, and additionally honours the affinity mask of the current thread
+/// and any cgroup v1 or v2 CPU bandwidth quota (`cpu.max` or `cpu.cfs_quota_us`), such as those imposed by container runtimes.
+///
+/// # Implementation
+///
+/// On Linux, this uses `sched_getaffinity` and reads the cgroup of the current process from `/proc/self/cgroup`.
+/// On other unix-like systems, this is the number of online processors reported by `sysconf`.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if the number of processors cannot be determined on the host.
+pub fn effective_parallelism() -> Result<core::num::NonZeroU32, crate::Error> {
+    crate::imp::effective_parallelism()
+        .map(|n| core::num::NonZeroU32::new(n).unwrap_or(core::num::NonZeroU32::MIN))
+        .map_err(crate::Error::from_raw_os_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(processor: &str) -> (Option<String>, Option<String>) {
+        gcc_llvm_names(processor, None)
+    }
+
+    fn both(gcc: &str, llvm: &str) -> (Option<String>, Option<String>) {
+        (Some(gcc.into()), Some(llvm.into()))
+    }
+
+    #[test]
+    fn power_names() {
+        assert_eq!(names("pwr8"), both("power8", "pwr8"));
+        assert_eq!(names("pwr9"), both("power9", "pwr9"));
+        assert_eq!(names("pwr10"), both("power10", "pwr10"));
+        assert_eq!(names("pwr5x"), both("power5+", "pwr5x"));
+        assert_eq!(names("e6500"), both("e6500", "e6500"));
+    }
+}
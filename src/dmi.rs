@@ -0,0 +1,195 @@
+use alloc::string::String;
+
+/// The type of enclosure of the system, from the SMBIOS System Enclosure (Type 3) structure.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChassisType {
+    /// Other
+    Other,
+    /// Unknown
+    Unknown,
+    /// Desktop
+    Desktop,
+    /// Low Profile Desktop
+    LowProfileDesktop,
+    /// Pizza Box
+    PizzaBox,
+    /// Mini Tower
+    MiniTower,
+    /// Tower
+    Tower,
+    /// Portable
+    Portable,
+    /// Laptop
+    Laptop,
+    /// Notebook
+    Notebook,
+    /// Hand Held
+    HandHeld,
+    /// Docking Station
+    DockingStation,
+    /// All in One
+    AllInOne,
+    /// Sub Notebook
+    SubNotebook,
+    /// Space-saving
+    SpaceSaving,
+    /// Lunch Box
+    LunchBox,
+    /// Main Server Chassis
+    MainServerChassis,
+    /// Expansion Chassis
+    ExpansionChassis,
+    /// SubChassis
+    SubChassis,
+    /// Bus Expansion Chassis
+    BusExpansionChassis,
+    /// Peripheral Chassis
+    PeripheralChassis,
+    /// RAID Chassis
+    RaidChassis,
+    /// Rack Mount Chassis
+    RackMountChassis,
+    /// Sealed-case PC
+    SealedCasePc,
+    /// Multi-system chassis
+    MultiSystemChassis,
+    /// Compact PCI
+    CompactPci,
+    /// Advanced TCA
+    AdvancedTca,
+    /// Blade
+    Blade,
+    /// Blade Enclosure
+    BladeEnclosure,
+    /// Tablet
+    Tablet,
+    /// Convertible
+    Convertible,
+    /// Detachable
+    Detachable,
+    /// IoT Gateway
+    IotGateway,
+    /// Embedded PC
+    EmbeddedPc,
+    /// Mini PC
+    MiniPc,
+    /// Stick PC
+    StickPc,
+}
+
+// Chassis types, in order of their SMBIOS code starting from 1
+const CHASSIS_TYPES: &[ChassisType] = &[
+    ChassisType::Other,
+    ChassisType::Unknown,
+    ChassisType::Desktop,
+    ChassisType::LowProfileDesktop,
+    ChassisType::PizzaBox,
+    ChassisType::MiniTower,
+    ChassisType::Tower,
+    ChassisType::Portable,
+    ChassisType::Laptop,
+    ChassisType::Notebook,
+    ChassisType::HandHeld,
+    ChassisType::DockingStation,
+    ChassisType::AllInOne,
+    ChassisType::SubNotebook,
+    ChassisType::SpaceSaving,
+    ChassisType::LunchBox,
+    ChassisType::MainServerChassis,
+    ChassisType::ExpansionChassis,
+    ChassisType::SubChassis,
+    ChassisType::BusExpansionChassis,
+    ChassisType::PeripheralChassis,
+    ChassisType::RaidChassis,
+    ChassisType::RackMountChassis,
+    ChassisType::SealedCasePc,
+    ChassisType::MultiSystemChassis,
+    ChassisType::CompactPci,
+    ChassisType::AdvancedTca,
+    ChassisType::Blade,
+    ChassisType::BladeEnclosure,
+    ChassisType::Tablet,
+    ChassisType::Convertible,
+    ChassisType::Detachable,
+    ChassisType::IotGateway,
+    ChassisType::EmbeddedPc,
+    ChassisType::MiniPc,
+    ChassisType::StickPc,
+];
+
+impl ChassisType {
+    /// Decodes an SMBIOS chassis type code (as found in `/sys/class/dmi/id/chassis_type`), ignoring the chassis lock bit.
+    pub fn from_code(code: u8) -> Option<Self> {
+        CHASSIS_TYPES
+            .get(usize::from(code & 0x7F).checked_sub(1)?)
+            .copied()
+    }
+
+    /// Returns the SMBIOS chassis type code.
+    pub fn code(self) -> u8 {
+        CHASSIS_TYPES.iter().position(|&ty| ty == self).unwrap() as u8 + 1
+    }
+}
+
+/// The identity of the system, as reported by the firmware in the SMBIOS (or DMI) tables.
+///
+/// Each field is `None` if the firmware does not report it, or (such as the serial numbers, which usually require root) it could not be read.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Dmi {
+    /// The manufacturer of the system, such as `Dell Inc.`
+    pub sys_vendor: Option<String>,
+    /// The product name of the system, such as `PowerEdge R750`
+    pub product_name: Option<String>,
+    /// The version of the system
+    pub product_version: Option<String>,
+    /// The serial number of the system
+    pub product_serial: Option<String>,
+    /// The manufacturer of the motherboard
+    pub board_vendor: Option<String>,
+    /// The product name of the motherboard
+    pub board_name: Option<String>,
+    /// The version of the motherboard
+    pub board_version: Option<String>,
+    /// The serial number of the motherboard
+    pub board_serial: Option<String>,
+    /// The vendor of the BIOS (or UEFI firmware)
+    pub bios_vendor: Option<String>,
+    /// The version of the BIOS, such as `1.9.2`
+    pub bios_version: Option<String>,
+    /// The release date of the BIOS, usually in the form `MM/DD/YYYY`
+    pub bios_date: Option<String>,
+    /// The type of enclosure
+    pub chassis_type: Option<ChassisType>,
+}
+
+impl Dmi {
+    /// Parses a raw SMBIOS structure table, such as the contents of `/sys/firmware/dmi/tables/DMI`.
+    pub fn from_smbios_table(table: &[u8]) -> Self {
+        crate::imp::smbios::decode_table(table)
+    }
+}
+
+/// Formats the system as its vendor, product name, and BIOS version, such as `Dell Inc. PowerEdge R750, BIOS 1.9.2`
+impl core::fmt::Display for Dmi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut sep = "";
+
+        for name in [&self.sys_vendor, &self.product_name].into_iter().flatten() {
+            f.write_str(sep)?;
+            f.write_str(name)?;
+            sep = " ";
+        }
+
+        if let Some(version) = &self.bios_version {
+            if !sep.is_empty() {
+                f.write_str(", ")?;
+            }
+            f.write_str("BIOS ")?;
+            f.write_str(version)?;
+        }
+
+        Ok(())
+    }
+}
@@ -44,11 +44,15 @@ impl FromRawOsError for ErrorKind {
             }),
             target_family = "unix" => ({
                 match raw {
-                    libc::ENOSYS => ErrorKind::Unsupported,
+                    crate::imp::UNSUPPORTED => ErrorKind::Unsupported,
                     _ => ErrorKind::__Uncategorized
                 }
             }),
-            target_family = "windows" => ErrorKind::__Uncategorized, // I don't think there are any others yet
+            target_family = "windows" => if raw == crate::imp::UNSUPPORTED {
+                ErrorKind::Unsupported
+            } else {
+                ErrorKind::__Uncategorized
+            },
             _ => if raw == 0xDEADBEEF { // Surely this error code will never show up on a real OS :ferrisClueless:
                 ErrorKind::Unsupported
             } else {
@@ -1,9 +1,134 @@
 use alloc::string::String;
 use core::ffi::CStr;
 
-pub fn bytes_to_string<const N: usize>(name: &[i8; N]) -> String {
+pub fn bytes_to_string<const N: usize>(name: &[core::ffi::c_char; N]) -> String {
     CStr::from_bytes_until_nul(bytemuck::bytes_of(name))
         .unwrap()
         .to_string_lossy()
         .into()
 }
+
+#[cfg(unix)]
+pub fn errno() -> i32 {
+    unsafe { errno_sys::errno_location().read() }
+}
+
+/// Reads the entire contents of the file at `path`
+#[cfg(unix)]
+pub fn read_file(path: &CStr) -> Result<alloc::vec::Vec<u8>, i32> {
+    let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC) };
+
+    if fd < 0 {
+        return Err(errno());
+    }
+
+    let mut buf = alloc::vec::Vec::with_capacity(4096);
+
+    loop {
+        if buf.len() == buf.capacity() {
+            buf.reserve(4096);
+        }
+
+        let spare = buf.spare_capacity_mut();
+
+        let len = unsafe { libc::read(fd, spare.as_mut_ptr().cast(), spare.len()) };
+
+        if len < 0 {
+            let err = errno();
+            if err == libc::EINTR {
+                continue;
+            }
+            unsafe { libc::close(fd) };
+            return Err(err);
+        } else if len == 0 {
+            break;
+        }
+
+        unsafe { buf.set_len(buf.len() + len as usize) }
+    }
+
+    unsafe { libc::close(fd) };
+
+    Ok(buf)
+}
+
+/// Reads at most the first `len` bytes of the file at `path`
+#[cfg(all(
+    unix,
+    any(
+        target_arch = "arm",
+        target_arch = "powerpc",
+        target_arch = "powerpc64"
+    )
+))]
+pub fn read_file_prefix(path: &CStr, len: usize) -> Result<alloc::vec::Vec<u8>, i32> {
+    let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC) };
+
+    if fd < 0 {
+        return Err(errno());
+    }
+
+    let mut buf = alloc::vec::Vec::with_capacity(len);
+
+    while buf.len() < len {
+        let remaining = len - buf.len();
+        let spare = &mut buf.spare_capacity_mut()[..remaining];
+
+        let n = unsafe { libc::read(fd, spare.as_mut_ptr().cast(), spare.len()) };
+
+        if n < 0 {
+            let err = errno();
+            if err == libc::EINTR {
+                continue;
+            }
+            unsafe { libc::close(fd) };
+            return Err(err);
+        } else if n == 0 {
+            break;
+        }
+
+        unsafe { buf.set_len(buf.len() + n as usize) }
+    }
+
+    unsafe { libc::close(fd) };
+
+    Ok(buf)
+}
+
+/// Reads the file at `path` as a string, with surrounding whitespace removed.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn read_file_string(path: &CStr) -> Result<String, i32> {
+    let bytes = read_file(path)?;
+
+    Ok(String::from_utf8_lossy(&bytes).trim().into())
+}
+
+/// Lists the names of the entries in the directory at `path`, other than `.` and `..`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn read_dir(path: &CStr) -> Result<alloc::vec::Vec<String>, i32> {
+    let dir = unsafe { libc::opendir(path.as_ptr()) };
+
+    if dir.is_null() {
+        return Err(errno());
+    }
+
+    let mut names = alloc::vec::Vec::new();
+
+    loop {
+        let ent = unsafe { libc::readdir(dir) };
+
+        if ent.is_null() {
+            break;
+        }
+
+        let name = unsafe { CStr::from_ptr((*ent).d_name.as_ptr()) };
+
+        if name != c"." && name != c".." {
+            names.push(name.to_string_lossy().into());
+        }
+    }
+
+    unsafe { libc::closedir(dir) };
+
+    Ok(names)
+}
@@ -0,0 +1,400 @@
+use alloc::vec::Vec;
+
+use crate::cpu::{Aarch64Features, ArmFeatures, FloatAbi};
+
+/// The implementer field of the Main ID Register (`MIDR_EL1`)
+pub const fn midr_implementer(midr: u32) -> u8 {
+    (midr >> 24) as u8
+}
+
+/// The variant field of the Main ID Register (`MIDR_EL1`)
+pub const fn midr_variant(midr: u32) -> u8 {
+    ((midr >> 20) & 0xF) as u8
+}
+
+/// The primary part number field of the Main ID Register (`MIDR_EL1`)
+pub const fn midr_part(midr: u32) -> u16 {
+    ((midr >> 4) & 0xFFF) as u16
+}
+
+// Parts designed by Arm, keyed by primary part number
+const ARM_PARTS: &[(u16, &str)] = &[
+    (0x926, "arm926ej-s"),
+    (0xb02, "mpcore"),
+    (0xb36, "arm1136j-s"),
+    (0xb56, "arm1156t2-s"),
+    (0xb76, "arm1176jz-s"),
+    (0xc05, "cortex-a5"),
+    (0xc07, "cortex-a7"),
+    (0xc08, "cortex-a8"),
+    (0xc09, "cortex-a9"),
+    (0xc0d, "cortex-a12"),
+    (0xc0e, "cortex-a17"),
+    (0xc0f, "cortex-a15"),
+    (0xc14, "cortex-r4"),
+    (0xc15, "cortex-r5"),
+    (0xc17, "cortex-r7"),
+    (0xc18, "cortex-r8"),
+    (0xc20, "cortex-m0"),
+    (0xc23, "cortex-m3"),
+    (0xc24, "cortex-m4"),
+    (0xc27, "cortex-m7"),
+    (0xd02, "cortex-a34"),
+    (0xd03, "cortex-a53"),
+    (0xd04, "cortex-a35"),
+    (0xd05, "cortex-a55"),
+    (0xd06, "cortex-a65"),
+    (0xd07, "cortex-a57"),
+    (0xd08, "cortex-a72"),
+    (0xd09, "cortex-a73"),
+    (0xd0a, "cortex-a75"),
+    (0xd0b, "cortex-a76"),
+    (0xd0c, "neoverse-n1"),
+    (0xd0d, "cortex-a77"),
+    (0xd0e, "cortex-a76ae"),
+    (0xd13, "cortex-r52"),
+    (0xd14, "cortex-r82ae"),
+    (0xd15, "cortex-r82"),
+    (0xd16, "cortex-r52plus"),
+    (0xd20, "cortex-m23"),
+    (0xd21, "cortex-m33"),
+    (0xd22, "cortex-m55"),
+    (0xd24, "cortex-m52"),
+    (0xd40, "neoverse-v1"),
+    (0xd41, "cortex-a78"),
+    (0xd42, "cortex-a78ae"),
+    (0xd43, "cortex-a65ae"),
+    (0xd44, "cortex-x1"),
+    (0xd46, "cortex-a510"),
+    (0xd47, "cortex-a710"),
+    (0xd48, "cortex-x2"),
+    (0xd49, "neoverse-n2"),
+    (0xd4a, "neoverse-e1"),
+    (0xd4b, "cortex-a78c"),
+    (0xd4c, "cortex-x1c"),
+    (0xd4d, "cortex-a715"),
+    (0xd4e, "cortex-x3"),
+    (0xd4f, "neoverse-v2"),
+    (0xd80, "cortex-a520"),
+    (0xd81, "cortex-a720"),
+    (0xd82, "cortex-x4"),
+    (0xd83, "neoverse-v3ae"),
+    (0xd84, "neoverse-v3"),
+    (0xd85, "cortex-x925"),
+    (0xd87, "cortex-a725"),
+    (0xd88, "cortex-a520ae"),
+    (0xd89, "cortex-a720ae"),
+    (0xd8e, "neoverse-n3"),
+];
+
+// Parts designed by Cavium (and Broadcom, which designed the Vulcan core that became the ThunderX2)
+const CAVIUM_PARTS: &[(u16, &str)] = &[
+    (0x0a0, "thunderx"),
+    (0x0a1, "thunderxt88"),
+    (0x0a2, "thunderxt81"),
+    (0x0a3, "thunderxt83"),
+    (0x0af, "thunderx2t99"),
+    (0x0b8, "thunderx3t110"),
+    (0x516, "thunderx2t99"),
+];
+
+const FUJITSU_PARTS: &[(u16, &str)] = &[(0x001, "a64fx"), (0x003, "fujitsu-monaka")];
+
+const HISILICON_PARTS: &[(u16, &str)] = &[(0xd01, "tsv110")];
+
+const NVIDIA_PARTS: &[(u16, &str)] = &[(0x004, "carmel"), (0x010, "olympus")];
+
+const QUALCOMM_PARTS: &[(u16, &str)] = &[
+    (0x001, "oryon-1"),
+    (0x06f, "krait"),
+    (0x201, "kryo"),
+    (0x205, "kryo"),
+    (0x211, "kryo"),
+    // Kryo 2xx, 3xx and 4xx are semi-custom Cortex cores
+    (0x800, "cortex-a73"),
+    (0x801, "cortex-a73"),
+    (0x802, "cortex-a75"),
+    (0x803, "cortex-a75"),
+    (0x804, "cortex-a76"),
+    (0x805, "cortex-a76"),
+    (0xc00, "falkor"),
+    (0xc01, "saphira"),
+];
+
+// Apple parts come in pairs of efficiency and performance cores per generation (and per die variant)
+const APPLE_PARTS: &[(u16, &str)] = &[
+    (0x020, "apple-m1"),
+    (0x021, "apple-m1"),
+    (0x022, "apple-m1"),
+    (0x023, "apple-m1"),
+    (0x024, "apple-m1"),
+    (0x025, "apple-m1"),
+    (0x028, "apple-m1"),
+    (0x029, "apple-m1"),
+    (0x030, "apple-m2"),
+    (0x031, "apple-m2"),
+    (0x032, "apple-m2"),
+    (0x033, "apple-m2"),
+    (0x034, "apple-m2"),
+    (0x035, "apple-m2"),
+    (0x038, "apple-m2"),
+    (0x039, "apple-m2"),
+    (0x048, "apple-m3"),
+    (0x049, "apple-m3"),
+];
+
+const MICROSOFT_PARTS: &[(u16, &str)] = &[(0xd49, "neoverse-n2")];
+
+const AMPERE_PARTS: &[(u16, &str)] =
+    &[(0xac3, "ampere1"), (0xac4, "ampere1a"), (0xac5, "ampere1b")];
+
+/// Maps the value of the Main ID Register (`MIDR_EL1`, or `MIDR` on 32-bit Arm) to the name LLVM uses for the processor,
+/// or `None` if the processor is not known.
+pub fn processor_from_midr(midr: u32) -> Option<&'static str> {
+    let part = midr_part(midr);
+
+    let parts = match midr_implementer(midr) {
+        0x41 => ARM_PARTS,
+        0x42 | 0x43 => CAVIUM_PARTS,
+        0x46 => FUJITSU_PARTS,
+        0x48 => HISILICON_PARTS,
+        0x4e => NVIDIA_PARTS,
+        0x51 => QUALCOMM_PARTS,
+        // Samsung's custom cores are distinguished by the variant as well as the part
+        0x53 => {
+            return match (midr_variant(midr), part) {
+                (1, 0x003) => Some("exynos-m4"),
+                _ => Some("exynos-m3"),
+            };
+        }
+        0x61 => APPLE_PARTS,
+        0x6d => MICROSOFT_PARTS,
+        0xc0 => AMPERE_PARTS,
+        _ => return None,
+    };
+
+    parts
+        .iter()
+        .find(|(id, _)| *id == part)
+        .map(|&(_, name)| name)
+}
+
+/// Parses the contents of `/proc/cpuinfo` into the value of the Main ID Register of each processor listed.
+///
+/// Processors that do not list their `CPU implementer` and `CPU part` are skipped.
+pub fn midrs_from_cpuinfo(cpuinfo: &str) -> Vec<u32> {
+    let mut midrs = Vec::new();
+
+    for block in cpuinfo.split("\n\n") {
+        let mut implementer = None;
+        let mut variant = 0;
+        let mut part = None;
+        let mut revision = 0;
+
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let value = value.trim();
+            let hex = || u32::from_str_radix(value.trim_start_matches("0x"), 16).ok();
+
+            match key.trim() {
+                "CPU implementer" => implementer = hex(),
+                "CPU variant" => variant = hex().unwrap_or(0),
+                "CPU part" => part = hex(),
+                "CPU revision" => revision = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        if let (Some(implementer), Some(part)) = (implementer, part) {
+            // The architecture field is always 0xF (defined by CPUID registers) on the processors Linux reports here
+            midrs.push(
+                (implementer << 24)
+                    | ((variant & 0xF) << 20)
+                    | (0xF << 16)
+                    | ((part & 0xFFF) << 4)
+                    | (revision & 0xF),
+            );
+        }
+    }
+
+    midrs
+}
+
+const HWCAP: usize = 0;
+const HWCAP2: usize = 1;
+
+// Each feature, and the `AT_HWCAP` or `AT_HWCAP2` bits that must all be set for it, from `asm/hwcap.h`
+const HWCAP_FEATURES: &[(Aarch64Features, usize, u64)] = &[
+    (Aarch64Features::NEON, HWCAP, (1 << 0) | (1 << 1)),
+    (Aarch64Features::AES, HWCAP, (1 << 3) | (1 << 4)),
+    (Aarch64Features::SHA2, HWCAP, (1 << 5) | (1 << 6)),
+    (Aarch64Features::CRC, HWCAP, 1 << 7),
+    (Aarch64Features::LSE, HWCAP, 1 << 8),
+    (Aarch64Features::FP16, HWCAP, (1 << 9) | (1 << 10)),
+    (Aarch64Features::RDM, HWCAP, 1 << 12),
+    (Aarch64Features::JSCONV, HWCAP, 1 << 13),
+    (Aarch64Features::FCMA, HWCAP, 1 << 14),
+    (Aarch64Features::RCPC, HWCAP, 1 << 15),
+    (Aarch64Features::DPB, HWCAP, 1 << 16),
+    (Aarch64Features::SHA3, HWCAP, (1 << 17) | (1 << 21)),
+    (Aarch64Features::SM4, HWCAP, (1 << 18) | (1 << 19)),
+    (Aarch64Features::DOTPROD, HWCAP, 1 << 20),
+    (Aarch64Features::SVE, HWCAP, 1 << 22),
+    (Aarch64Features::FHM, HWCAP, 1 << 23),
+    (Aarch64Features::DIT, HWCAP, 1 << 24),
+    (Aarch64Features::LSE2, HWCAP, 1 << 25),
+    (Aarch64Features::RCPC2, HWCAP, 1 << 26),
+    (Aarch64Features::FLAGM, HWCAP, 1 << 27),
+    (Aarch64Features::SSBS, HWCAP, 1 << 28),
+    (Aarch64Features::SB, HWCAP, 1 << 29),
+    (Aarch64Features::PACA, HWCAP, 1 << 30),
+    (Aarch64Features::PACG, HWCAP, 1 << 31),
+    (Aarch64Features::DPB2, HWCAP2, 1 << 0),
+    (Aarch64Features::SVE2, HWCAP2, 1 << 1),
+    (Aarch64Features::SVE2_AES, HWCAP2, (1 << 2) | (1 << 3)),
+    (Aarch64Features::SVE2_BITPERM, HWCAP2, 1 << 4),
+    (Aarch64Features::SVE2_SHA3, HWCAP2, 1 << 5),
+    (Aarch64Features::SVE2_SM4, HWCAP2, 1 << 6),
+    (Aarch64Features::FLAGM2, HWCAP2, 1 << 7),
+    (Aarch64Features::FRINTTS, HWCAP2, 1 << 8),
+    (Aarch64Features::F32MM, HWCAP2, 1 << 10),
+    (Aarch64Features::F64MM, HWCAP2, 1 << 11),
+    (Aarch64Features::I8MM, HWCAP2, 1 << 13),
+    (Aarch64Features::BF16, HWCAP2, 1 << 14),
+    (Aarch64Features::RAND, HWCAP2, 1 << 16),
+    (Aarch64Features::BTI, HWCAP2, 1 << 17),
+    (Aarch64Features::MTE, HWCAP2, 1 << 18),
+    (Aarch64Features::ECV, HWCAP2, 1 << 19),
+    (Aarch64Features::SME, HWCAP2, 1 << 23),
+    (Aarch64Features::WFXT, HWCAP2, 1 << 31),
+    (Aarch64Features::CSSC, HWCAP2, 1 << 34),
+    (Aarch64Features::SVE2P1, HWCAP2, 1 << 36),
+    (Aarch64Features::SME2, HWCAP2, 1 << 37),
+    (Aarch64Features::MOPS, HWCAP2, 1 << 43),
+    (Aarch64Features::HBC, HWCAP2, 1 << 44),
+    (Aarch64Features::RCPC3, HWCAP2, 1 << 46),
+    (Aarch64Features::LSE128, HWCAP2, 1 << 47),
+    (Aarch64Features::LUT, HWCAP2, 1 << 49),
+    (Aarch64Features::FAMINMAX, HWCAP2, 1 << 50),
+];
+
+/// Decodes the `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries reported by Linux on AArch64
+pub fn features_from_hwcap(hwcap: u64, hwcap2: u64) -> Aarch64Features {
+    let words = [hwcap, hwcap2];
+
+    HWCAP_FEATURES
+        .iter()
+        .filter(|&&(_, word, bits)| words[word] & bits == bits)
+        .fold(Aarch64Features::empty(), |features, &(feature, _, _)| {
+            features | feature
+        })
+}
+
+// Each feature, and the `AT_HWCAP` or `AT_HWCAP2` bits that must all be set for it, from `asm/hwcap.h` on 32-bit Arm
+const ARM_HWCAP_FEATURES: &[(ArmFeatures, usize, u32)] = &[
+    (ArmFeatures::VFP2, HWCAP, 1 << 6),
+    (ArmFeatures::DSP, HWCAP, 1 << 7),
+    (ArmFeatures::NEON, HWCAP, 1 << 12),
+    (ArmFeatures::VFP3, HWCAP, 1 << 13),
+    (ArmFeatures::VFP3, HWCAP, 1 << 14),
+    (ArmFeatures::VFP4, HWCAP, 1 << 16),
+    (ArmFeatures::D32, HWCAP, 1 << 19),
+    (ArmFeatures::DOTPROD, HWCAP, 1 << 24),
+    (ArmFeatures::I8MM, HWCAP, 1 << 27),
+    (ArmFeatures::AES, HWCAP2, (1 << 0) | (1 << 1)),
+    (ArmFeatures::SHA2, HWCAP2, (1 << 2) | (1 << 3)),
+    (ArmFeatures::CRC, HWCAP2, 1 << 4),
+];
+
+/// Decodes the `AT_HWCAP` and `AT_HWCAP2` auxiliary vector entries reported by Linux on 32-bit Arm
+pub fn arm_features_from_hwcap(version: u8, hwcap: u32, hwcap2: u32) -> ArmFeatures {
+    let words = [hwcap, hwcap2];
+
+    let mut features = ARM_HWCAP_FEATURES
+        .iter()
+        .filter(|&&(_, word, bits)| words[word] & bits == bits)
+        .fold(ArmFeatures::empty(), |features, &(feature, _, _)| {
+            features | feature
+        });
+
+    // Armv6T2 can't be told apart from Armv6, so thumb2 is only reported from Armv7
+    let versions = [
+        (5, ArmFeatures::V5TE),
+        (6, ArmFeatures::V6),
+        (7, ArmFeatures::V6T2 | ArmFeatures::THUMB2 | ArmFeatures::V7),
+        (8, ArmFeatures::V8),
+    ];
+
+    for (min, feature) in versions {
+        if version >= min {
+            features |= feature;
+        }
+    }
+
+    features
+}
+
+const EM_ARM: u16 = 40;
+
+const EF_ARM_EABIMASK: u32 = 0xFF00_0000;
+const EF_ARM_EABI_VER5: u32 = 0x0500_0000;
+const EF_ARM_ABI_FLOAT_HARD: u32 = 0x0000_0400;
+
+/// Reads the float ABI from the `e_flags` of a 32-bit Arm ELF header
+pub fn float_abi_from_elf_header(header: &[u8]) -> Option<FloatAbi> {
+    let header = header.get(..40)?;
+
+    // ELFCLASS32, and either byte order
+    if header[..5] != *b"\x7fELF\x01" {
+        return None;
+    }
+
+    let (machine, flags) = match header[5] {
+        1 => (
+            u16::from_le_bytes([header[18], header[19]]),
+            u32::from_le_bytes([header[36], header[37], header[38], header[39]]),
+        ),
+        2 => (
+            u16::from_be_bytes([header[18], header[19]]),
+            u32::from_be_bytes([header[36], header[37], header[38], header[39]]),
+        ),
+        _ => return None,
+    };
+
+    if machine != EM_ARM {
+        return None;
+    }
+
+    // Before EABI version 5, the same bit means the (soft-float) VFP format rather than the hard-float ABI
+    if flags & EF_ARM_EABIMASK >= EF_ARM_EABI_VER5 && flags & EF_ARM_ABI_FLOAT_HARD != 0 {
+        Some(FloatAbi::Hard)
+    } else {
+        Some(FloatAbi::Soft)
+    }
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    #[cfg(target_arch = "aarch64")]
+    let platform = super::aarch64_features().map(|features| features.architecture().into());
+
+    #[cfg(target_arch = "arm")]
+    let platform = super::arm_info()
+        .ok()
+        .map(|info| alloc::format!("armv{}", info.version));
+
+    x.hardware_platform = platform.unwrap_or_else(|| x.machine.clone());
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub fn populate_processor(x: &mut crate::Uname) {
+    use alloc::string::ToString;
+
+    x.processor = match super::midr().and_then(processor_from_midr) {
+        Some(name) => name.to_string(),
+        None => x.machine.clone(),
+    };
+}
@@ -0,0 +1,227 @@
+use alloc::string::String;
+
+use crate::virt::{Container, ContainerRuntime};
+
+/// Looks up `name` in a NUL-separated environment block, such as `/proc/self/environ`
+pub fn env_var<'a>(environ: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    environ
+        .split(|&b| b == 0)
+        .find_map(|var| var.strip_prefix(name.as_bytes())?.strip_prefix(b"="))
+}
+
+/// Finds the first run of 64 hex digits, which is how Docker, Podman, and containerd name containers
+pub fn find_container_id(s: &str) -> Option<&str> {
+    let bytes = s.as_bytes();
+    let mut start = 0;
+
+    for (i, b) in bytes.iter().chain(b"/").enumerate() {
+        if !b.is_ascii_hexdigit() {
+            if i - start == 64 {
+                return Some(&s[start..i]);
+            }
+            start = i + 1;
+        }
+    }
+
+    None
+}
+
+/// Finds the ID of a Docker container in a cgroup path, which is either `/docker/<id>` or `docker-<id>.scope`.
+///
+/// The Docker daemon itself runs in `docker.service`, so only a full ID identifies a container
+fn docker_cgroup_id(path: &str) -> Option<&str> {
+    path.split('/')
+        .zip(path.split('/').skip(1))
+        .find_map(|(parent, part)| {
+            let id = match parent {
+                "docker" => part,
+                _ => part.strip_prefix("docker-")?.strip_suffix(".scope")?,
+            };
+            (id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())).then_some(id)
+        })
+}
+
+/// Maps the `container=` variable set by container managers for their init process
+pub fn runtime_from_container_var(value: &str) -> ContainerRuntime {
+    match value {
+        "docker" => ContainerRuntime::Docker,
+        "podman" => ContainerRuntime::Podman,
+        "lxc" | "lxc-libvirt" => ContainerRuntime::Lxc,
+        "systemd-nspawn" => ContainerRuntime::SystemdNspawn,
+        "wsl" => ContainerRuntime::Wsl,
+        _ => ContainerRuntime::Other,
+    }
+}
+
+/// Identifies the container from the paths in `/proc/self/cgroup`, which are only visible if the container does not have its own cgroup namespace
+pub fn from_cgroup(cgroup: &str) -> Option<Container> {
+    cgroup.lines().find_map(|line| {
+        // Such as `0::/system.slice/docker-<id>.scope` or `12:memory:/docker/<id>`
+        let path = line.splitn(3, ':').nth(2)?;
+
+        let runtime = if path.contains("/kubepods") {
+            ContainerRuntime::Kubernetes
+        } else if let Some(id) = docker_cgroup_id(path) {
+            return Some(Container {
+                runtime: ContainerRuntime::Docker,
+                id: Some(id.into()),
+            });
+        } else if path.contains("/libpod") {
+            ContainerRuntime::Podman
+        } else if let Some(name) = path
+            .split('/')
+            .find_map(|part| part.strip_prefix("lxc.payload."))
+            .or_else(|| path.strip_prefix("/lxc/")?.split('/').next())
+        {
+            return Some(Container {
+                runtime: ContainerRuntime::Lxc,
+                id: Some(name.into()),
+            });
+        } else {
+            return None;
+        };
+
+        Some(Container {
+            runtime,
+            id: find_container_id(path).map(Into::into),
+        })
+    })
+}
+
+/// Identifies the container from the sources of the mounts in `/proc/self/mountinfo`, which show where the runtime keeps the container's files on the host
+pub fn from_mountinfo(mountinfo: &str) -> Option<Container> {
+    mountinfo.lines().find_map(|line| {
+        // The 4th field is the root of the mount within its filesystem
+        let root = line.split_whitespace().nth(3)?;
+
+        let runtime =
+            if root.contains("/kubelet/pods/") || root.contains("/io.containerd.grpc.v1.cri/") {
+                ContainerRuntime::Kubernetes
+            } else if root.contains("/docker/containers/") {
+                ContainerRuntime::Docker
+            } else if root.contains("/containers/storage/overlay-containers/") {
+                ContainerRuntime::Podman
+            } else {
+                return None;
+            };
+
+        Some(Container {
+            runtime,
+            id: find_container_id(root).map(Into::into),
+        })
+    })
+}
+
+/// Reads the `key="value"` lines of `/run/.containerenv`
+fn containerenv_field<'a>(containerenv: &'a str, key: &str) -> Option<&'a str> {
+    containerenv.lines().find_map(|line| {
+        let value = line.strip_prefix(key)?.strip_prefix('=')?.trim_matches('"');
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+/// Reads the `name=` key of the `[Application]` group of `/.flatpak-info`
+fn flatpak_app_id(info: &str) -> Option<&str> {
+    let mut in_application = false;
+
+    for line in info.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_application = line == "[Application]";
+        } else if in_application && let Some(name) = line.strip_prefix("name=") {
+            return Some(name);
+        }
+    }
+
+    None
+}
+
+#[cfg(unix)]
+pub fn detect(root: &str) -> Option<Container> {
+    let root = root.trim_end_matches('/');
+
+    let read = |path: &str| {
+        let path = alloc::ffi::CString::new(alloc::format!("{root}{path}")).ok()?;
+        crate::helper::read_file(&path).ok()
+    };
+    let read_string = |path: &str| read(path).map(|v| String::from_utf8_lossy(&v).into_owned());
+
+    let environ = read("/proc/self/environ").unwrap_or_default();
+    let env = |name: &str| env_var(&environ, name).map(|v| String::from_utf8_lossy(v).into_owned());
+
+    let container = |runtime, id: Option<String>| Some(Container { runtime, id });
+
+    if let Some(info) = read_string("/.flatpak-info") {
+        return container(
+            ContainerRuntime::Flatpak,
+            flatpak_app_id(&info).map(Into::into),
+        );
+    }
+
+    if let Some(name) = env("SNAP_NAME") {
+        return container(ContainerRuntime::Snap, Some(name));
+    }
+
+    // Kubernetes runs pods with a container runtime (which may be Docker), so it is checked first
+    if env("KUBERNETES_SERVICE_HOST").is_some() {
+        let id = read_string("/proc/self/cgroup")
+            .and_then(|cgroup| find_container_id(&cgroup).map(Into::into))
+            .or_else(|| env("HOSTNAME"));
+        return container(ContainerRuntime::Kubernetes, id);
+    }
+
+    if let Some(containerenv) = read_string("/run/.containerenv") {
+        let id = containerenv_field(&containerenv, "id")
+            .or_else(|| containerenv_field(&containerenv, "name"));
+        return container(ContainerRuntime::Podman, id.map(Into::into));
+    }
+
+    let cgroup = read_string("/proc/self/cgroup").and_then(|cgroup| from_cgroup(&cgroup));
+    let mountinfo =
+        read_string("/proc/self/mountinfo").and_then(|mountinfo| from_mountinfo(&mountinfo));
+
+    // The ID of a Docker container usually only appears in the mounts of `/etc/hostname` and `/etc/resolv.conf`
+    let id_for = |runtime| {
+        [&cgroup, &mountinfo]
+            .into_iter()
+            .flatten()
+            .find(|c| c.runtime == runtime && c.id.is_some())
+            .and_then(|c| c.id.clone())
+    };
+
+    if read("/.dockerenv").is_some() {
+        return container(ContainerRuntime::Docker, id_for(ContainerRuntime::Docker));
+    }
+
+    // systemd-nspawn and LXC set `container=` for init, which systemd copies to `/run/systemd/container`
+    if let Some(value) = read_string("/run/systemd/container")
+        .map(|v| v.trim().into())
+        .or_else(|| {
+            read("/proc/1/environ").and_then(|environ| {
+                Some(String::from_utf8_lossy(env_var(&environ, "container")?).into_owned())
+            })
+        })
+    {
+        let runtime = runtime_from_container_var(&value);
+        let id = match runtime {
+            ContainerRuntime::Wsl => env("WSL_DISTRO_NAME"),
+            _ => id_for(runtime),
+        };
+        return container(runtime, id);
+    }
+
+    if cgroup.is_some() {
+        return cgroup;
+    }
+
+    if mountinfo.is_some() {
+        return mountinfo;
+    }
+
+    // WSL distributions run in a container (on WSL2, a container in a utility VM), but don't set `container=`
+    if let Some(wsl) = super::wsl::detect(root) {
+        return container(ContainerRuntime::Wsl, wsl.distro_name);
+    }
+
+    None
+}
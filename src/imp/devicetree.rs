@@ -0,0 +1,304 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::board::{Board, RaspberryPiRevision};
+
+// Boards, by the most specific `compatible` string, and their vendor and name
+const BOARDS: &[(&str, &str, &str)] = &[
+    // NVIDIA Jetson
+    ("nvidia,p2371-2180", "NVIDIA", "Jetson TX1"),
+    ("nvidia,p2771-0000", "NVIDIA", "Jetson TX2"),
+    ("nvidia,p3509-0000+p3636-0001", "NVIDIA", "Jetson TX2 NX"),
+    ("nvidia,p3450-0000", "NVIDIA", "Jetson Nano"),
+    ("nvidia,p3541-0000", "NVIDIA", "Jetson Nano 2GB"),
+    ("nvidia,p2972-0000", "NVIDIA", "Jetson AGX Xavier"),
+    ("nvidia,p3509-0000+p3668-0000", "NVIDIA", "Jetson Xavier NX"),
+    ("nvidia,p3509-0000+p3668-0001", "NVIDIA", "Jetson Xavier NX"),
+    ("nvidia,p3737-0000+p3701-0000", "NVIDIA", "Jetson AGX Orin"),
+    (
+        "nvidia,p3737-0000+p3701-0004",
+        "NVIDIA",
+        "Jetson AGX Orin 32GB",
+    ),
+    (
+        "nvidia,p3737-0000+p3701-0005",
+        "NVIDIA",
+        "Jetson AGX Orin 64GB",
+    ),
+    (
+        "nvidia,p3768-0000+p3767-0000",
+        "NVIDIA",
+        "Jetson Orin NX 16GB",
+    ),
+    (
+        "nvidia,p3768-0000+p3767-0001",
+        "NVIDIA",
+        "Jetson Orin NX 8GB",
+    ),
+    (
+        "nvidia,p3768-0000+p3767-0003",
+        "NVIDIA",
+        "Jetson Orin Nano 8GB",
+    ),
+    (
+        "nvidia,p3768-0000+p3767-0004",
+        "NVIDIA",
+        "Jetson Orin Nano 4GB",
+    ),
+    ("nvidia,p3768-0000+p3767-0005", "NVIDIA", "Jetson Orin Nano"),
+    // Rockchip SoCs
+    ("pine64,rockpro64", "Pine64", "ROCKPro64"),
+    ("pine64,pinebook-pro", "Pine64", "Pinebook Pro"),
+    ("pine64,quartz64-a", "Pine64", "Quartz64 Model A"),
+    ("radxa,rockpi4b", "Radxa", "ROCK Pi 4B"),
+    ("radxa,rock-4se", "Radxa", "ROCK 4SE"),
+    ("radxa,rock-3a", "Radxa", "ROCK 3A"),
+    ("radxa,rock-5a", "Radxa", "ROCK 5A"),
+    ("radxa,rock-5b", "Radxa", "ROCK 5B"),
+    ("friendlyarm,nanopi-r4s", "FriendlyElec", "NanoPi R4S"),
+    ("friendlyarm,nanopi-r5s", "FriendlyElec", "NanoPi R5S"),
+    ("friendlyarm,nanopi-r6s", "FriendlyElec", "NanoPi R6S"),
+    ("xunlong,orangepi-5", "Xunlong", "Orange Pi 5"),
+    ("xunlong,orangepi-5-plus", "Xunlong", "Orange Pi 5 Plus"),
+    ("hardkernel,odroid-m1", "Hardkernel", "ODROID-M1"),
+    // StarFive
+    ("starfive,visionfive-v1", "StarFive", "VisionFive"),
+    (
+        "starfive,visionfive-2-v1.2a",
+        "StarFive",
+        "VisionFive 2 v1.2A",
+    ),
+    (
+        "starfive,visionfive-2-v1.3b",
+        "StarFive",
+        "VisionFive 2 v1.3B",
+    ),
+    (
+        "beagle,beaglev-starlight-jh7100-r0",
+        "BeagleBoard.org",
+        "BeagleV Starlight",
+    ),
+    ("milkv,mars", "Milk-V", "Mars"),
+    ("pine64,star64", "Pine64", "Star64"),
+    // Apple Silicon, as named by Asahi Linux
+    ("apple,j274", "Apple", "Mac mini (M1, 2020)"),
+    ("apple,j293", "Apple", "MacBook Pro (13-inch, M1, 2020)"),
+    ("apple,j313", "Apple", "MacBook Air (M1, 2020)"),
+    ("apple,j456", "Apple", "iMac (24-inch, 4x USB-C, M1, 2021)"),
+    ("apple,j457", "Apple", "iMac (24-inch, 2x USB-C, M1, 2021)"),
+    (
+        "apple,j314s",
+        "Apple",
+        "MacBook Pro (14-inch, M1 Pro, 2021)",
+    ),
+    (
+        "apple,j314c",
+        "Apple",
+        "MacBook Pro (14-inch, M1 Max, 2021)",
+    ),
+    (
+        "apple,j316s",
+        "Apple",
+        "MacBook Pro (16-inch, M1 Pro, 2021)",
+    ),
+    (
+        "apple,j316c",
+        "Apple",
+        "MacBook Pro (16-inch, M1 Max, 2021)",
+    ),
+    ("apple,j375c", "Apple", "Mac Studio (M1 Max, 2022)"),
+    ("apple,j375d", "Apple", "Mac Studio (M1 Ultra, 2022)"),
+    ("apple,j413", "Apple", "MacBook Air (M2, 2022)"),
+    ("apple,j493", "Apple", "MacBook Pro (13-inch, M2, 2022)"),
+    ("apple,j415", "Apple", "MacBook Air (15-inch, M2, 2023)"),
+    ("apple,j473", "Apple", "Mac mini (M2, 2023)"),
+    ("apple,j474s", "Apple", "Mac mini (M2 Pro, 2023)"),
+    (
+        "apple,j414s",
+        "Apple",
+        "MacBook Pro (14-inch, M2 Pro, 2023)",
+    ),
+    (
+        "apple,j414c",
+        "Apple",
+        "MacBook Pro (14-inch, M2 Max, 2023)",
+    ),
+    (
+        "apple,j416s",
+        "Apple",
+        "MacBook Pro (16-inch, M2 Pro, 2023)",
+    ),
+    (
+        "apple,j416c",
+        "Apple",
+        "MacBook Pro (16-inch, M2 Max, 2023)",
+    ),
+    ("apple,j475c", "Apple", "Mac Studio (M2 Max, 2023)"),
+    ("apple,j475d", "Apple", "Mac Studio (M2 Ultra, 2023)"),
+    ("apple,j180d", "Apple", "Mac Pro (M2 Ultra, 2023)"),
+];
+
+// Vendors, by the prefix of any `compatible` string, for boards that are not known
+const VENDOR_PREFIXES: &[(&str, &str)] = &[
+    ("raspberrypi,", "Raspberry Pi"),
+    ("nvidia,", "NVIDIA"),
+    ("apple,", "Apple"),
+    ("starfive,", "StarFive"),
+    ("rockchip,", "Rockchip"),
+];
+
+// New-style Raspberry Pi revision codes: the type (bits 4-11)
+const RASPBERRY_PI_TYPES: &[(u32, &str)] = &[
+    (0x00, "Model A"),
+    (0x01, "Model B"),
+    (0x02, "Model A+"),
+    (0x03, "Model B+"),
+    (0x04, "2 Model B"),
+    (0x06, "Compute Module"),
+    (0x08, "3 Model B"),
+    (0x09, "Zero"),
+    (0x0a, "Compute Module 3"),
+    (0x0c, "Zero W"),
+    (0x0d, "3 Model B+"),
+    (0x0e, "3 Model A+"),
+    (0x10, "Compute Module 3+"),
+    (0x11, "4 Model B"),
+    (0x12, "Zero 2 W"),
+    (0x13, "400"),
+    (0x14, "Compute Module 4"),
+    (0x15, "Compute Module 4S"),
+    (0x17, "5"),
+    (0x18, "Compute Module 5"),
+    (0x19, "500"),
+    (0x1a, "Compute Module 5 Lite"),
+];
+
+// The processor (bits 12-15)
+const RASPBERRY_PI_PROCESSORS: &[&str] = &["BCM2835", "BCM2836", "BCM2837", "BCM2711", "BCM2712"];
+
+// The manufacturer (bits 16-19)
+const RASPBERRY_PI_MANUFACTURERS: &[&str] = &[
+    "Sony UK",
+    "Egoman",
+    "Embest",
+    "Sony Japan",
+    "Embest",
+    "Stadium",
+];
+
+// An old-style revision code, and the model, revision, memory size, and manufacturer
+type OldRevision = (u32, &'static str, (u8, u8), u32, &'static str);
+
+const RASPBERRY_PI_OLD_REVISIONS: &[OldRevision] = &[
+    (0x0002, "Model B", (1, 0), 256, "Egoman"),
+    (0x0003, "Model B", (1, 0), 256, "Egoman"),
+    (0x0004, "Model B", (2, 0), 256, "Sony UK"),
+    (0x0005, "Model B", (2, 0), 256, "Qisda"),
+    (0x0006, "Model B", (2, 0), 256, "Egoman"),
+    (0x0007, "Model A", (2, 0), 256, "Egoman"),
+    (0x0008, "Model A", (2, 0), 256, "Sony UK"),
+    (0x0009, "Model A", (2, 0), 256, "Qisda"),
+    (0x000d, "Model B", (2, 0), 512, "Egoman"),
+    (0x000e, "Model B", (2, 0), 512, "Sony UK"),
+    (0x000f, "Model B", (2, 0), 512, "Egoman"),
+    (0x0010, "Model B+", (1, 2), 512, "Sony UK"),
+    (0x0011, "Compute Module", (1, 0), 512, "Sony UK"),
+    (0x0012, "Model A+", (1, 1), 256, "Sony UK"),
+    (0x0013, "Model B+", (1, 2), 512, "Embest"),
+    (0x0014, "Compute Module", (1, 0), 512, "Embest"),
+    (0x0015, "Model A+", (1, 1), 256, "Embest"),
+];
+
+const RASPBERRY_PI_NEW_STYLE: u32 = 1 << 23;
+
+pub fn decode_raspberry_pi_revision(code: u32) -> RaspberryPiRevision {
+    if code & RASPBERRY_PI_NEW_STYLE == 0 {
+        // Bit 24 is set if the board has been overvolted
+        let old = RASPBERRY_PI_OLD_REVISIONS
+            .iter()
+            .find(|&&(c, ..)| c == code & 0xFFFF);
+
+        return RaspberryPiRevision {
+            code,
+            model: old.map(|&(_, model, ..)| model),
+            revision: old.map_or((0, 0), |&(_, _, revision, ..)| revision),
+            memory_mb: old.map(|&(.., memory, _)| memory),
+            processor: old.map(|_| "BCM2835"),
+            manufacturer: old.map(|&(.., manufacturer)| manufacturer),
+        };
+    }
+
+    let ty = (code >> 4) & 0xFF;
+    let processor = (code >> 12) & 0xF;
+    let manufacturer = (code >> 16) & 0xF;
+    let memory = (code >> 20) & 0x7;
+
+    RaspberryPiRevision {
+        code,
+        model: RASPBERRY_PI_TYPES
+            .iter()
+            .find(|&&(t, _)| t == ty)
+            .map(|&(_, model)| model),
+        revision: (1, (code & 0xF) as u8),
+        memory_mb: (memory <= 6).then(|| 256 << memory),
+        processor: RASPBERRY_PI_PROCESSORS.get(processor as usize).copied(),
+        manufacturer: RASPBERRY_PI_MANUFACTURERS
+            .get(manufacturer as usize)
+            .copied(),
+    }
+}
+
+pub fn is_raspberry_pi(compatible: &[String]) -> bool {
+    compatible.iter().any(|c| c.starts_with("raspberrypi,"))
+}
+
+pub fn identify(model: Option<&str>, compatible: &[u8], revision: Option<u32>) -> Board {
+    let compatible: Vec<String> = compatible
+        .split(|&b| b == 0)
+        .filter(|c| !c.is_empty())
+        .map(|c| String::from_utf8_lossy(c).into())
+        .collect();
+
+    let model = model
+        .map(|model| model.trim_end_matches('\0').trim())
+        .filter(|model| !model.is_empty())
+        .map(String::from);
+
+    let raspberry_pi = revision
+        .filter(|_| is_raspberry_pi(&compatible))
+        .map(decode_raspberry_pi_revision);
+
+    let known = compatible
+        .iter()
+        .find_map(|c| BOARDS.iter().find(|(compat, ..)| compat == c));
+
+    let vendor = match known {
+        Some(&(_, vendor, _)) => Some(vendor),
+        None => compatible.iter().find_map(|c| {
+            VENDOR_PREFIXES
+                .iter()
+                .find(|(prefix, _)| c.starts_with(prefix))
+                .map(|&(_, vendor)| vendor)
+        }),
+    };
+
+    let name = match (known, raspberry_pi) {
+        (Some(&(_, _, name)), _) => Some(name.into()),
+        (
+            None,
+            Some(RaspberryPiRevision {
+                model: Some(model),
+                revision: (major, minor),
+                ..
+            }),
+        ) => Some(alloc::format!("Raspberry Pi {model} Rev {major}.{minor}")),
+        (None, _) => None,
+    };
+
+    Board {
+        model,
+        compatible,
+        vendor,
+        name,
+        raspberry_pi,
+    }
+}
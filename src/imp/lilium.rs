@@ -11,6 +11,8 @@ use lilium_sys::sys::{
 
 use crate::Uname;
 
+pub const UNSUPPORTED: isize = lilium_sys::sys::result::errors::UNSUPPORTED_KERNEL_FUNCTION;
+
 pub fn populate_uname(v: &mut Uname) -> Result<(), isize> {
     let mut os_vendor = String::with_capacity(32);
     let mut kernel_vendor = String::with_capacity(32);
@@ -131,7 +133,10 @@ pub fn populate_uname(v: &mut Uname) -> Result<(), isize> {
         }
         arch_info::ARCH_TYPE_AARCH64 => {
             v.machine = "aarch64".to_string();
-            super::populate_hardware_platform(v); // TODO: Same here
+            v.hardware_platform = match arch_info.arch_version {
+                0 => "armv8-a".to_string(),
+                minor => alloc::format!("armv8.{minor}-a"),
+            };
         }
         arch_info::ARCH_TYPE_RISCV32 => {
             v.machine = "riscv32".to_string();
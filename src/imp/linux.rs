@@ -0,0 +1,787 @@
+use alloc::{collections::BTreeMap, ffi::CString, string::String, vec::Vec};
+
+use crate::{
+    Memory,
+    cpu::{Cache, CacheType, CoreType, LogicalCpu, Topology},
+    helper::{read_dir, read_file_string},
+};
+
+/// Parses a size from sysfs, such as `32K` or `8M`, into bytes
+fn parse_size(size: &str) -> Option<u64> {
+    let (num, mult) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 1024),
+        b'M' => (&size[..size.len() - 1], 1024 * 1024),
+        b'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+
+    num.parse::<u64>().ok().map(|v| v * mult)
+}
+
+/// Parses a cpu list from sysfs, such as `0-3,8-11`, into the individual cpu numbers
+pub fn parse_cpu_list(list: &str) -> impl Iterator<Item = u32> + '_ {
+    list.split(',')
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| {
+            let (lo, hi) = range.split_once('-').unwrap_or((range, range));
+            let lo = lo.trim().parse::<u32>().unwrap_or(1);
+            let hi = hi.trim().parse::<u32>().unwrap_or(0);
+            lo..=hi
+        })
+}
+
+pub fn caches() -> Result<Vec<Cache>, i32> {
+    let mut caches = Vec::new();
+
+    for idx in 0.. {
+        let read = |name: &str| {
+            let path = alloc::format!("/sys/devices/system/cpu/cpu0/cache/index{idx}/{name}");
+            read_file_string(&CString::new(path).unwrap())
+        };
+
+        let level = match read("level") {
+            Ok(level) => level,
+            Err(libc::ENOENT) if idx > 0 => break,
+            Err(e) => return Err(e),
+        };
+
+        let cache_type = match &*read("type")? {
+            "Data" => CacheType::Data,
+            "Instruction" => CacheType::Instruction,
+            _ => CacheType::Unified,
+        };
+
+        let ways = read("ways_of_associativity")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        caches.push(Cache {
+            level: level.parse().unwrap_or(0),
+            cache_type,
+            size: read("size").ok().and_then(|v| parse_size(&v)).unwrap_or(0),
+            line_size: read("coherency_line_size")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            associativity: if ways == 0 { None } else { Some(ways) },
+            shared_by: read("shared_cpu_list")
+                .map(|v| parse_cpu_list(&v).count() as u32)
+                .unwrap_or(0),
+        });
+    }
+
+    Ok(caches)
+}
+
+/// Runs `f` on the logical processor `cpu`, by temporarily restricting the affinity of the current thread to `cpu`
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn on_cpu<R>(cpu: u32, f: impl FnOnce() -> R) -> Result<R, i32> {
+    let size = core::mem::size_of::<libc::cpu_set_t>();
+    let mut old: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+    let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+
+    if unsafe { libc::sched_getaffinity(0, size, &mut old) } < 0 {
+        return Err(crate::helper::errno());
+    }
+
+    unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+
+    if unsafe { libc::sched_setaffinity(0, size, &set) } < 0 {
+        return Err(crate::helper::errno());
+    }
+
+    let res = f();
+
+    unsafe { libc::sched_setaffinity(0, size, &old) };
+
+    Ok(res)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn fallback_core_types(cpus: &[u32]) -> Vec<CoreType> {
+    if !super::x86::is_hybrid() {
+        return alloc::vec![CoreType::Performance; cpus.len()];
+    }
+
+    cpus.iter()
+        .map(|&cpu| on_cpu(cpu, super::x86::current_core_type).unwrap_or(CoreType::Unknown))
+        .collect()
+}
+
+/// Classifies cores by their `cpu_capacity`, where the cores with the highest capacity are the performance cores
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn fallback_core_types(cpus: &[u32]) -> Vec<CoreType> {
+    let capacities: Vec<Option<u32>> = cpus
+        .iter()
+        .map(|cpu| {
+            let path = alloc::format!("/sys/devices/system/cpu/cpu{cpu}/cpu_capacity");
+            read_file_string(&CString::new(path).unwrap())
+                .ok()
+                .and_then(|v| v.parse().ok())
+        })
+        .collect();
+
+    let max = capacities.iter().flatten().copied().max();
+
+    capacities
+        .into_iter()
+        .map(|cap| match (cap, max) {
+            (Some(cap), Some(max)) if cap == max => CoreType::Performance,
+            (Some(_), _) => CoreType::Efficiency,
+            (None, None) => CoreType::Performance,
+            (None, Some(_)) => CoreType::Unknown,
+        })
+        .collect()
+}
+
+pub fn logical_cpus() -> Result<Vec<LogicalCpu>, i32> {
+    let online = read_file_string(c"/sys/devices/system/cpu/online")?;
+    let cpus: Vec<u32> = parse_cpu_list(&online).collect();
+
+    let pcores = read_file_string(c"/sys/devices/cpu_core/cpus").ok();
+    let ecores = read_file_string(c"/sys/devices/cpu_atom/cpus").ok();
+
+    let contains = |list: &Option<String>, cpu| {
+        list.as_deref()
+            .is_some_and(|l| parse_cpu_list(l).any(|c| c == cpu))
+    };
+
+    let core_types = if pcores.is_some() || ecores.is_some() {
+        cpus.iter()
+            .map(|&cpu| {
+                if contains(&pcores, cpu) {
+                    CoreType::Performance
+                } else if contains(&ecores, cpu) {
+                    CoreType::Efficiency
+                } else {
+                    CoreType::Unknown
+                }
+            })
+            .collect()
+    } else {
+        fallback_core_types(&cpus)
+    };
+
+    let core_names = cfg_match::cfg_match! {
+        any(target_arch = "x86", target_arch = "x86_64") => super::x86::hybrid_core_names(),
+        _ => None::<(&'static str, &'static str)>,
+    };
+
+    Ok(core::iter::zip(cpus, core_types)
+        .map(|(cpu, core_type)| {
+            let topology = |name: &str| {
+                let path = alloc::format!("/sys/devices/system/cpu/cpu{cpu}/topology/{name}");
+                read_file_string(&CString::new(path).unwrap())
+                    .ok()
+                    .and_then(|v| v.parse::<u32>().ok())
+            };
+
+            LogicalCpu {
+                cpu,
+                core_type,
+                core_name: match (core_type, core_names) {
+                    (CoreType::Performance, Some((pcore, _))) => Some(pcore),
+                    (CoreType::Efficiency, Some((_, ecore))) => Some(ecore),
+                    _ => None,
+                },
+                package: topology("physical_package_id"),
+                core: topology("core_id"),
+            }
+        })
+        .collect())
+}
+
+/// Finds the directories of the cgroup the current process belongs to for `controller`, from the cgroup itself up to the root of the hierarchy.
+///
+/// Returns the directories, and whether the hierarchy is a cgroup v2 (unified) hierarchy.
+fn cgroup_dirs(controller: &str) -> Option<(Vec<String>, bool)> {
+    let cgroups = read_file_string(c"/proc/self/cgroup").ok()?;
+    let mountinfo = read_file_string(c"/proc/self/mountinfo").ok()?;
+
+    // Prefer a cgroup v1 hierarchy with the controller, which takes precedence over the unified hierarchy in hybrid setups
+    let mut lines: Vec<(bool, &str)> = cgroups
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let id = parts.next()?;
+            let controllers = parts.next()?;
+            let path = parts.next()?;
+
+            if id == "0" && controllers.is_empty() {
+                Some((true, path))
+            } else if controllers.split(',').any(|c| c == controller) {
+                Some((false, path))
+            } else {
+                None
+            }
+        })
+        .collect();
+    lines.sort_by_key(|&(v2, _)| v2);
+
+    for (v2, path) in lines {
+        for mount in mountinfo.lines() {
+            let Some((pre, post)) = mount.split_once(" - ") else {
+                continue;
+            };
+            let mut pre = pre.split(' ').skip(3);
+            let (Some(root), Some(mount_point)) = (pre.next(), pre.next()) else {
+                continue;
+            };
+            let mut post = post.split(' ');
+            let fstype = post.next().unwrap_or("");
+            let options = post.nth(1).unwrap_or("");
+
+            let matches = if v2 {
+                fstype == "cgroup2"
+            } else {
+                fstype == "cgroup" && options.split(',').any(|o| o == controller)
+            };
+
+            if !matches {
+                continue;
+            }
+
+            // If the cgroup is outside the mounted root (such as inside a container), the mount point is the closest visible cgroup
+            let rel = if root == "/" {
+                path
+            } else {
+                path.strip_prefix(root).unwrap_or("")
+            };
+
+            let mut rel = rel.trim_end_matches('/');
+            let mut dirs = Vec::new();
+
+            loop {
+                dirs.push(alloc::format!("{mount_point}{rel}"));
+                match rel.rfind('/') {
+                    Some(idx) => rel = &rel[..idx],
+                    None => break,
+                }
+            }
+
+            return Some((dirs, v2));
+        }
+    }
+
+    None
+}
+
+/// Determines the CPU bandwidth limit (quota divided by period, rounded up) imposed by the cgroup of the current process, if any
+fn cgroup_cpu_limit() -> Option<u32> {
+    let (dirs, v2) = cgroup_dirs("cpu")?;
+
+    let read = |dir: &str, name: &str| {
+        read_file_string(&CString::new(alloc::format!("{dir}/{name}")).unwrap()).ok()
+    };
+
+    dirs.iter()
+        .filter_map(|dir| {
+            let (quota, period) = if v2 {
+                let max = read(dir, "cpu.max")?;
+                let (quota, period) = max.split_once(' ')?;
+                (quota.parse::<u64>().ok()?, period.parse::<u64>().ok()?)
+            } else {
+                (
+                    read(dir, "cpu.cfs_quota_us")?.parse::<u64>().ok()?,
+                    read(dir, "cpu.cfs_period_us")?.parse::<u64>().ok()?,
+                )
+            };
+
+            if period == 0 {
+                return None;
+            }
+
+            Some(quota.div_ceil(period) as u32)
+        })
+        .min()
+}
+
+pub fn topology() -> Result<Topology, i32> {
+    let online = read_file_string(c"/sys/devices/system/cpu/online")?;
+    let possible = read_file_string(c"/sys/devices/system/cpu/possible")?;
+    let online: Vec<u32> = parse_cpu_list(&online).collect();
+
+    let mut cores = BTreeMap::<(u32, u32), u32>::new();
+
+    for &cpu in &online {
+        let read = |name: &str| {
+            let path = alloc::format!("/sys/devices/system/cpu/cpu{cpu}/topology/{name}");
+            read_file_string(&CString::new(path).unwrap()).ok()
+        };
+
+        let package = read("physical_package_id")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        // `core_id` is only unique within a cluster on Arm, so cores are identified by their lowest numbered thread instead
+        let core = read("core_cpus_list")
+            .or_else(|| read("thread_siblings_list"))
+            .and_then(|list| parse_cpu_list(&list).min())
+            .unwrap_or(cpu);
+
+        *cores.entry((package, core)).or_default() += 1;
+    }
+
+    let mut sockets = BTreeMap::<u32, u32>::new();
+
+    for &(package, _) in cores.keys() {
+        *sockets.entry(package).or_default() += 1;
+    }
+
+    Ok(Topology {
+        sockets: sockets.len() as u32,
+        cores_per_socket: sockets.values().copied().max().unwrap_or(0),
+        threads_per_core: cores.values().copied().max().unwrap_or(0),
+        online,
+        possible: parse_cpu_list(&possible).collect(),
+    })
+}
+
+pub fn effective_parallelism() -> Result<u32, i32> {
+    let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+
+    let affinity =
+        if unsafe { libc::sched_getaffinity(0, core::mem::size_of::<libc::cpu_set_t>(), &mut set) }
+            < 0
+        {
+            let online = read_file_string(c"/sys/devices/system/cpu/online")?;
+            parse_cpu_list(&online).count() as u32
+        } else {
+            unsafe { libc::CPU_COUNT(&set) as u32 }
+        };
+
+    Ok(match cgroup_cpu_limit() {
+        Some(limit) => affinity.min(limit),
+        None => affinity,
+    })
+}
+
+/// Determines the memory limit imposed by the cgroup of the current process, if any
+fn cgroup_memory_limit() -> Option<u64> {
+    let (dirs, v2) = cgroup_dirs("memory")?;
+
+    let name = if v2 {
+        "memory.max"
+    } else {
+        "memory.limit_in_bytes"
+    };
+
+    // cgroup v1 reports an unlimited cgroup as the largest `i64` rounded down to the page size, which is not always 4 KiB
+    let page_size = match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        ..=0 => 4096,
+        n => n as u64,
+    };
+    let unlimited = i64::MAX as u64 & !(page_size - 1);
+
+    dirs.iter()
+        .filter_map(|dir| {
+            read_file_string(&CString::new(alloc::format!("{dir}/{name}")).unwrap())
+                .ok()?
+                .parse::<u64>()
+                .ok()
+        })
+        .filter(|&limit| limit < unlimited)
+        .min()
+}
+
+pub fn memory() -> Result<Memory, i32> {
+    let mut memory = super::os_memory()?;
+
+    let page_size = unsafe { libc::getauxval(libc::AT_PAGESZ) };
+
+    if page_size != 0 {
+        memory.page_size = page_size as u64;
+    }
+
+    if let Ok(meminfo) = read_file_string(c"/proc/meminfo") {
+        memory.available = meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemAvailable:"))
+            .and_then(|v| v.trim().strip_suffix("kB"))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(|kb| kb * 1024);
+    }
+
+    if let Ok(entries) = read_dir(c"/sys/kernel/mm/hugepages") {
+        memory.huge_page_sizes = entries
+            .iter()
+            .filter_map(|name| name.strip_prefix("hugepages-")?.strip_suffix("kB"))
+            .filter_map(|kb| kb.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+            .collect();
+        memory.huge_page_sizes.sort_unstable();
+    }
+
+    memory.cgroup_limit = cgroup_memory_limit();
+
+    Ok(memory)
+}
+
+/// Reads the Main ID Register of the most capable online processor, from sysfs or (on older kernels) `/proc/cpuinfo`
+#[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+pub fn midr() -> Option<u32> {
+    let mut best: Option<(u32, u32)> = None;
+
+    if let Ok(online) = read_file_string(c"/sys/devices/system/cpu/online") {
+        for cpu in parse_cpu_list(&online) {
+            let read = |name: &str| {
+                let path = alloc::format!("/sys/devices/system/cpu/cpu{cpu}/{name}");
+                read_file_string(&CString::new(path).unwrap())
+            };
+
+            let Some(midr) = read("regs/identification/midr_el1")
+                .ok()
+                .and_then(|v| u64::from_str_radix(v.trim_start_matches("0x"), 16).ok())
+            else {
+                continue;
+            };
+
+            // On big.LITTLE systems, name the processor after the big cores, which are usually numbered last
+            let capacity = read("cpu_capacity")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(0);
+
+            if best.is_none_or(|(best, _)| capacity >= best) {
+                best = Some((capacity, midr as u32));
+            }
+        }
+    }
+
+    match best {
+        Some((_, midr)) => Some(midr),
+        None => {
+            let cpuinfo = crate::helper::read_file(c"/proc/cpuinfo").ok()?;
+            super::arm::midrs_from_cpuinfo(&String::from_utf8_lossy(&cpuinfo))
+                .last()
+                .copied()
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+pub fn aarch64_features() -> crate::cpu::Aarch64Features {
+    let hwcap = unsafe { libc::getauxval(libc::AT_HWCAP) };
+    let hwcap2 = unsafe { libc::getauxval(libc::AT_HWCAP2) };
+
+    super::arm::features_from_hwcap(hwcap, hwcap2)
+}
+
+#[cfg(target_arch = "arm")]
+pub fn arm_info() -> Result<crate::cpu::ArmInfo, i32> {
+    use crate::cpu::{ArmFeatures, ArmInfo};
+
+    let platform = unsafe { libc::getauxval(libc::AT_PLATFORM) } as *const core::ffi::c_char;
+
+    if platform.is_null() {
+        return Err(libc::ENOENT);
+    }
+
+    // Such as `v7l`, or `v5b` on big-endian
+    let platform = unsafe { core::ffi::CStr::from_ptr(platform) }.to_string_lossy();
+    let version = platform
+        .trim_start_matches('v')
+        .trim_end_matches(|c: char| !c.is_ascii_digit())
+        .parse::<u8>()
+        .map_err(|_| libc::EINVAL)?;
+
+    let hwcap = unsafe { libc::getauxval(libc::AT_HWCAP) } as u32;
+    let hwcap2 = unsafe { libc::getauxval(libc::AT_HWCAP2) } as u32;
+
+    // The loader's ELF header is mapped at `AT_BASE`. Statically linked programs have no loader, and only their own header to go by
+    let base = unsafe { libc::getauxval(libc::AT_BASE) };
+    let float_abi = if base != 0 {
+        let header = unsafe { core::slice::from_raw_parts(base as *const u8, 40) };
+        super::arm::float_abi_from_elf_header(header)
+    } else {
+        crate::helper::read_file_prefix(c"/proc/self/exe", 40)
+            .ok()
+            .and_then(|exe| super::arm::float_abi_from_elf_header(&exe))
+    };
+
+    Ok(ArmInfo {
+        version,
+        features: ArmFeatures::from_hwcap(version, hwcap, hwcap2),
+        float_abi,
+    })
+}
+
+/// Queries `riscv_hwprobe` for the value of each of `keys` common to every online processor, or `None` for keys the kernel doesn't know
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+fn riscv_hwprobe<const N: usize>(keys: [i64; N]) -> [Option<u64>; N] {
+    #[repr(C)]
+    struct RiscvHwprobe {
+        key: i64,
+        value: u64,
+    }
+
+    const SYS_RISCV_HWPROBE: libc::c_long = 258;
+
+    let mut pairs = keys.map(|key| RiscvHwprobe { key, value: 0 });
+
+    // An empty cpu set asks for the values common to every online processor
+    let res = unsafe {
+        libc::syscall(
+            SYS_RISCV_HWPROBE,
+            pairs.as_mut_ptr(),
+            pairs.len(),
+            0usize,
+            core::ptr::null_mut::<libc::c_void>(),
+            0u32,
+        )
+    };
+
+    // The kernel sets the key to -1 for keys it doesn't know
+    pairs.map(|pair| (res == 0 && pair.key != -1).then_some(pair.value))
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+fn riscv_cpuinfo() -> Option<String> {
+    crate::helper::read_file(c"/proc/cpuinfo")
+        .ok()
+        .map(|cpuinfo| String::from_utf8_lossy(&cpuinfo).into())
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub fn riscv_isa() -> Result<crate::cpu::RiscvIsa, i32> {
+    const KEY_BASE_BEHAVIOR: i64 = 3;
+    const KEY_IMA_EXT_0: i64 = 4;
+
+    let cpuinfo = riscv_cpuinfo().and_then(|cpuinfo| super::riscv::isa_from_cpuinfo(&cpuinfo));
+
+    match riscv_hwprobe([KEY_BASE_BEHAVIOR, KEY_IMA_EXT_0]) {
+        [base, Some(ima_ext_0)] => Ok(super::riscv::isa_from_hwprobe(
+            base.unwrap_or(0),
+            ima_ext_0,
+            cpuinfo,
+        )),
+        _ => cpuinfo.ok_or(libc::ENOENT),
+    }
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub fn riscv_processor() -> Option<&'static str> {
+    const KEY_MVENDORID: i64 = 0;
+    const KEY_MARCHID: i64 = 1;
+    const KEY_MIMPID: i64 = 2;
+
+    let cpuinfo = riscv_cpuinfo().unwrap_or_default();
+
+    let by_id = match riscv_hwprobe([KEY_MVENDORID, KEY_MARCHID, KEY_MIMPID]) {
+        [Some(mvendorid), Some(marchid), Some(mimpid)] => {
+            super::riscv::processor_from_ids(mvendorid, marchid, mimpid)
+        }
+        _ => None,
+    };
+
+    by_id.or_else(|| super::riscv::processor_from_cpuinfo(&cpuinfo))
+}
+
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+pub fn power_info() -> Result<crate::cpu::PowerInfo, i32> {
+    let platform = |ty| {
+        let ptr = unsafe { libc::getauxval(ty) } as *const core::ffi::c_char;
+        (!ptr.is_null()).then(|| unsafe { core::ffi::CStr::from_ptr(ptr) }.to_string_lossy())
+    };
+
+    let base_platform = platform(libc::AT_BASE_PLATFORM);
+    let platform = platform(libc::AT_PLATFORM).ok_or(libc::ENOENT)?;
+
+    let hwcap = unsafe { libc::getauxval(libc::AT_HWCAP) } as u64;
+    let hwcap2 = unsafe { libc::getauxval(libc::AT_HWCAP2) } as u64;
+
+    // The loader's ELF header is mapped at `AT_BASE`. Statically linked programs have no loader, and only their own header to go by
+    let base = unsafe { libc::getauxval(libc::AT_BASE) };
+    let exe;
+    let header = if base != 0 {
+        Some(unsafe { core::slice::from_raw_parts(base as *const u8, 52) })
+    } else {
+        exe = crate::helper::read_file_prefix(c"/proc/self/exe", 52).ok();
+        exe.as_deref()
+    };
+
+    Ok(super::powerpc::decode(
+        &platform,
+        base_platform.as_deref(),
+        hwcap,
+        hwcap2,
+        header,
+    ))
+}
+
+#[cfg(target_arch = "s390x")]
+pub fn s390x_info() -> Result<crate::cpu::S390xInfo, i32> {
+    let cpuinfo = crate::helper::read_file(c"/proc/cpuinfo")?;
+    let hwcap = unsafe { libc::getauxval(libc::AT_HWCAP) };
+
+    Ok(super::s390x::decode(
+        &String::from_utf8_lossy(&cpuinfo),
+        hwcap,
+    ))
+}
+
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "mips32r6",
+    target_arch = "mips64r6"
+))]
+pub fn mips_info() -> Result<crate::cpu::MipsInfo, i32> {
+    let cpuinfo = crate::helper::read_file(c"/proc/cpuinfo")?;
+
+    super::mips::decode_cpuinfo(&String::from_utf8_lossy(&cpuinfo)).ok_or(libc::ENOENT)
+}
+
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+pub fn sparc_info() -> Result<crate::cpu::SparcInfo, i32> {
+    let cpuinfo = crate::helper::read_file(c"/proc/cpuinfo")?;
+
+    super::sparc::decode_cpuinfo(&String::from_utf8_lossy(&cpuinfo)).ok_or(libc::ENOENT)
+}
+
+pub fn dmi() -> Result<crate::dmi::Dmi, i32> {
+    let read = |name: &str| {
+        let path = alloc::format!("/sys/class/dmi/id/{name}");
+        read_file_string(&CString::new(path).unwrap())
+            .ok()
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut dmi = crate::dmi::Dmi {
+        sys_vendor: read("sys_vendor"),
+        product_name: read("product_name"),
+        product_version: read("product_version"),
+        product_serial: read("product_serial"),
+        board_vendor: read("board_vendor"),
+        board_name: read("board_name"),
+        board_version: read("board_version"),
+        board_serial: read("board_serial"),
+        bios_vendor: read("bios_vendor"),
+        bios_version: read("bios_version"),
+        bios_date: read("bios_date"),
+        chassis_type: read("chassis_type")
+            .and_then(|v| v.parse().ok())
+            .and_then(crate::dmi::ChassisType::from_code),
+    };
+
+    // The raw table is only readable by root, but is still present if the kernel doesn't provide `/sys/class/dmi/id`
+    match crate::helper::read_file(c"/sys/firmware/dmi/tables/DMI") {
+        Ok(table) => super::smbios::merge(&mut dmi, super::smbios::decode_table(&table)),
+        Err(err) if dmi == crate::dmi::Dmi::default() => return Err(err),
+        Err(_) => {}
+    }
+
+    Ok(dmi)
+}
+
+pub fn board() -> Result<crate::board::Board, i32> {
+    let read = |name: &str| {
+        ["/proc/device-tree", "/sys/firmware/devicetree/base"]
+            .iter()
+            .find_map(|base| {
+                let path = alloc::format!("{base}/{name}");
+                crate::helper::read_file(&CString::new(path).unwrap()).ok()
+            })
+    };
+
+    let compatible = read("compatible").ok_or(libc::ENOENT)?;
+    let model = read("model").map(|model| String::from_utf8_lossy(&model).into_owned());
+
+    // Raspberry Pi kernels report the board revision in `/proc/cpuinfo`, and newer firmware also puts it in the device tree
+    let revision = crate::helper::read_file(c"/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            let cpuinfo = crate::linux::Cpuinfo::parse(&String::from_utf8_lossy(&cpuinfo));
+            u32::from_str_radix(cpuinfo.shared.get("Revision")?, 16).ok()
+        })
+        .or_else(|| {
+            let revision = read("system/linux,revision")?;
+            Some(u32::from_be_bytes(revision.get(..4)?.try_into().ok()?))
+        });
+
+    Ok(crate::imp::devicetree::identify(
+        model.as_deref(),
+        &compatible,
+        revision,
+    ))
+}
+
+pub fn virtualization() -> Result<crate::virt::Virtualization, i32> {
+    use super::virt;
+    use crate::virt::Virtualization;
+
+    let cpuid = cfg_match::cfg_match! {
+        any(target_arch = "x86", target_arch = "x86_64") => super::x86::hypervisor_signature().map(virt::from_cpuid_signature),
+        _ => None,
+    };
+
+    // Xen PV guests can't see the hypervisor in cpuid, and dom0 is the host rather than a guest
+    if read_file_string(c"/sys/hypervisor/type").is_ok_and(|ty| ty == "xen") {
+        if read_file_string(c"/proc/xen/capabilities").is_ok_and(|caps| caps.contains("control_d"))
+        {
+            return Ok(Virtualization::None);
+        }
+
+        return Ok(
+            match read_file_string(c"/sys/hypervisor/guest_type").as_deref() {
+                Ok("PV") => Virtualization::XenPv,
+                Ok(_) => Virtualization::XenHvm,
+                Err(_)
+                    if cpuid == Some(Virtualization::XenHvm)
+                        || !cfg!(any(target_arch = "x86", target_arch = "x86_64")) =>
+                {
+                    Virtualization::XenHvm
+                }
+                Err(_) => Virtualization::XenPv,
+            },
+        );
+    }
+
+    let dmi = dmi().ok();
+
+    let has_cpuid = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+
+    match virt::combine(has_cpuid, cpuid, dmi.as_ref().and_then(virt::from_dmi)) {
+        // Firecracker has neither SMBIOS tables nor a PCI bus
+        Some(Virtualization::Kvm)
+            if dmi.is_none()
+                && read_dir(c"/sys/bus/pci/devices").is_ok_and(|devices| devices.is_empty()) =>
+        {
+            return Ok(Virtualization::Firecracker);
+        }
+        Some(virt) => return Ok(virt),
+        None => {}
+    }
+
+    let device_tree = |name: &core::ffi::CStr| crate::helper::read_file(name).ok();
+
+    if let Some(virt) = device_tree(c"/proc/device-tree/hypervisor/compatible")
+        .and_then(|compatible| virt::from_device_tree_hypervisor(&compatible))
+    {
+        return Ok(virt);
+    }
+
+    if device_tree(c"/proc/device-tree/ibm,partition-name").is_some()
+        && device_tree(c"/proc/device-tree/hmc-managed?").is_some()
+        && device_tree(c"/proc/device-tree/chosen/qemu,graphic-width").is_none()
+    {
+        return Ok(Virtualization::PowerVm);
+    }
+
+    // QEMU's firmware configuration device
+    if read_dir(c"/proc/device-tree")
+        .is_ok_and(|nodes| nodes.iter().any(|node| node.starts_with("fw-cfg")))
+    {
+        return Ok(Virtualization::Qemu);
+    }
+
+    if let Some(virt) = read_file_string(c"/proc/sysinfo")
+        .ok()
+        .and_then(|sysinfo| virt::from_s390_sysinfo(&sysinfo))
+    {
+        return Ok(virt);
+    }
+
+    Ok(Virtualization::None)
+}
@@ -0,0 +1,166 @@
+use crate::cpu::{LoongArchFeatures, LoongArchInfo};
+
+const PRID_COMP_MASK: u32 = 0x00FF_0000;
+const PRID_COMP_LOONGSON: u32 = 0x0014_0000;
+const PRID_SERIES_MASK: u32 = 0xF000;
+
+// Loongson processor series, by the series field of PRID (`CPUCFG` word 0)
+const PRID_SERIES: &[(u32, &str)] = &[
+    (0xA000, "la264"),
+    (0xB000, "la364"),
+    (0xC000, "la464"),
+    (0xD000, "la664"),
+];
+
+pub fn processor_from_prid(prid: u32) -> Option<&'static str> {
+    if prid & PRID_COMP_MASK != PRID_COMP_LOONGSON {
+        return None;
+    }
+
+    PRID_SERIES
+        .iter()
+        .find(|(series, _)| *series == prid & PRID_SERIES_MASK)
+        .map(|&(_, name)| name)
+}
+
+// Each feature, and the `CPUCFG` word and bit that reports it
+const CPUCFG_FEATURES: &[(LoongArchFeatures, usize, u32)] = &[
+    (LoongArchFeatures::UAL, 1, 1 << 20),
+    (LoongArchFeatures::F, 2, 1 << 1),
+    (LoongArchFeatures::D, 2, 1 << 2),
+    (LoongArchFeatures::LSX, 2, 1 << 6),
+    (LoongArchFeatures::LASX, 2, 1 << 7),
+    (LoongArchFeatures::LVZ, 2, 1 << 10),
+    (LoongArchFeatures::LBT, 2, (1 << 18) | (1 << 19) | (1 << 20)),
+    (LoongArchFeatures::FRECIPE, 2, 1 << 25),
+    (LoongArchFeatures::DIV32, 2, 1 << 26),
+    (LoongArchFeatures::LAM_BH, 2, 1 << 27),
+    (LoongArchFeatures::LAMCAS, 2, 1 << 28),
+    (LoongArchFeatures::SCQ, 2, 1 << 30),
+    (LoongArchFeatures::LD_SEQ_SA, 3, 1 << 23),
+];
+
+const CPUCFG1_ARCH_MASK: u32 = 0x3;
+const CPUCFG1_ARCH_LA64: u32 = 0x2;
+
+/// Decodes `CPUCFG` words 0 to 3
+pub fn decode_cpucfg(words: [u32; 4]) -> LoongArchInfo {
+    let features = CPUCFG_FEATURES
+        .iter()
+        .filter(|&&(_, word, bits)| words[word] & bits == bits)
+        .fold(LoongArchFeatures::empty(), |features, &(feature, _, _)| {
+            features | feature
+        });
+
+    LoongArchInfo {
+        prid: Some(words[0]),
+        grlen: if words[1] & CPUCFG1_ARCH_MASK == CPUCFG1_ARCH_LA64 {
+            64
+        } else {
+            32
+        },
+        features,
+    }
+}
+
+// The names Linux uses for features on the `Features` line of `/proc/cpuinfo`
+const CPUINFO_FEATURES: &[(&str, LoongArchFeatures)] = &[
+    ("ual", LoongArchFeatures::UAL),
+    ("fpu", LoongArchFeatures::F.union(LoongArchFeatures::D)),
+    ("lsx", LoongArchFeatures::LSX),
+    ("lasx", LoongArchFeatures::LASX),
+    ("lvz", LoongArchFeatures::LVZ),
+    ("frecipe", LoongArchFeatures::FRECIPE),
+    ("div32", LoongArchFeatures::DIV32),
+    ("lam_bh", LoongArchFeatures::LAM_BH),
+    ("lamcas", LoongArchFeatures::LAMCAS),
+    ("scq", LoongArchFeatures::SCQ),
+    ("ld_seq_sa", LoongArchFeatures::LD_SEQ_SA),
+];
+
+/// Decodes the `PRID`, `ISA` and `Features` lines of `/proc/cpuinfo`
+pub fn decode_cpuinfo(cpuinfo: &str) -> LoongArchInfo {
+    let field = |name: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim())
+        })
+    };
+
+    // Such as `Loongson-64bit (0014c012)`
+    let prid = field("PRID")
+        .and_then(|prid| prid.rsplit_once('('))
+        .and_then(|(_, prid)| u32::from_str_radix(prid.trim_end_matches(')'), 16).ok());
+
+    let grlen = match field("ISA") {
+        Some(isa) if isa.split_whitespace().any(|isa| isa == "loongarch64") => 64,
+        _ => 32,
+    };
+
+    let features = field("Features")
+        .unwrap_or("")
+        .split_whitespace()
+        .filter_map(|name| CPUINFO_FEATURES.iter().find(|(n, _)| *n == name))
+        .fold(LoongArchFeatures::empty(), |features, &(_, feature)| {
+            features | feature
+        });
+
+    LoongArchInfo {
+        prid,
+        grlen,
+        features,
+    }
+}
+
+const LA64V1_0: LoongArchFeatures = LoongArchFeatures::UAL
+    .union(LoongArchFeatures::F)
+    .union(LoongArchFeatures::D)
+    .union(LoongArchFeatures::LSX);
+
+const LA64V1_1: LoongArchFeatures = LA64V1_0
+    .union(LoongArchFeatures::FRECIPE)
+    .union(LoongArchFeatures::DIV32)
+    .union(LoongArchFeatures::LAM_BH)
+    .union(LoongArchFeatures::LAMCAS)
+    .union(LoongArchFeatures::SCQ)
+    .union(LoongArchFeatures::LD_SEQ_SA);
+
+pub fn architecture(info: &LoongArchInfo) -> Option<&'static str> {
+    if info.grlen != 64 {
+        None
+    } else if info.features.contains(LA64V1_1) {
+        Some("la64v1.1")
+    } else if info.features.contains(LA64V1_0) {
+        Some("la64v1.0")
+    } else {
+        None
+    }
+}
+
+/// Reads `CPUCFG` words 0 to 3, which are available to userspace
+#[cfg(target_arch = "loongarch64")]
+pub fn cpucfg() -> [u32; 4] {
+    core::array::from_fn(|word| {
+        let value: usize;
+        unsafe {
+            core::arch::asm!("cpucfg {0}, {1}", out(reg) value, in(reg) word, options(nomem, nostack, preserves_flags));
+        }
+        value as u32
+    })
+}
+
+#[cfg(target_arch = "loongarch64")]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match decode_cpucfg(cpucfg()).processor() {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(target_arch = "loongarch64")]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match architecture(&decode_cpucfg(cpucfg())) {
+        Some(arch) => arch.into(),
+        None => x.machine.clone(),
+    };
+}
@@ -0,0 +1,144 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::cpu::MipsInfo;
+
+// Cores named by `cpu model` after the `MIPS ` prefix, as GCC spells them
+const MIPS_CORES: &[&str] = &[
+    "4kc",
+    "4km",
+    "4kp",
+    "4ksc",
+    "4kec",
+    "4kem",
+    "4kep",
+    "4ksd",
+    "5kc",
+    "5kf",
+    "20kc",
+    "24kc",
+    "24kec",
+    "34kc",
+    "34kn",
+    "74kc",
+    "1004kc",
+    "interaptiv",
+    "m4k",
+    "m14k",
+    "m14kc",
+    "m14ke",
+    "m14kec",
+    "m5100",
+    "m5101",
+    "p5600",
+    "i6400",
+    "i6500",
+    "p6600",
+];
+
+// Other vendors' processors, by a prefix of `cpu model`
+const MODEL_PREFIXES: &[(&str, &str)] = &[
+    ("Cavium Octeon III", "octeon3"),
+    ("Cavium Octeon II", "octeon2"),
+    ("Cavium Octeon+", "octeon+"),
+    ("Cavium Octeon", "octeon"),
+    ("ICT Loongson-2 V0.2", "loongson2e"),
+    ("ICT Loongson-2 V0.3", "loongson2f"),
+    ("ICT Loongson-3", "loongson3a"),
+    ("Loongson-2E", "loongson2e"),
+    ("Loongson-2F", "loongson2f"),
+    ("Loongson-3A R1", "loongson3a"),
+    ("Loongson-3A R2", "gs464e"),
+    ("Loongson-3A R3", "gs464e"),
+    ("Loongson-3B R2", "gs464e"),
+    ("Loongson-3", "loongson3a"),
+];
+
+pub fn processor_from_model(model: &str) -> Option<&'static str> {
+    if let Some(core) = model.strip_prefix("MIPS ") {
+        let core = core.split_whitespace().next()?.to_ascii_lowercase();
+        return MIPS_CORES.iter().find(|&&c| c == core).copied();
+    }
+
+    MODEL_PREFIXES
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|&(_, name)| name)
+}
+
+// ISA revisions, as Linux spells them on the `isa` line and as `-march` spells them, from oldest to newest
+const ISA_REVISIONS: &[(&str, &str)] = &[
+    ("mips1", "mips1"),
+    ("mips2", "mips2"),
+    ("mips32r1", "mips32"),
+    ("mips32r2", "mips32r2"),
+    ("mips32r3", "mips32r3"),
+    ("mips32r5", "mips32r5"),
+    ("mips32r6", "mips32r6"),
+    ("mips3", "mips3"),
+    ("mips4", "mips4"),
+    ("mips5", "mips5"),
+    ("mips64r1", "mips64"),
+    ("mips64r2", "mips64r2"),
+    ("mips64r3", "mips64r3"),
+    ("mips64r5", "mips64r5"),
+    ("mips64r6", "mips64r6"),
+];
+
+/// Finds the newest ISA revision on the `isa` line, preferring 64-bit revisions
+pub fn isa_from_list(isa: &str) -> Option<&'static str> {
+    isa.split_whitespace()
+        .filter_map(|isa| ISA_REVISIONS.iter().position(|&(name, _)| name == isa))
+        .max()
+        .map(|idx| ISA_REVISIONS[idx].1)
+}
+
+pub fn decode_cpuinfo(cpuinfo: &str) -> Option<MipsInfo> {
+    let field = |name: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim())
+        })
+    };
+
+    let model = field("cpu model")?;
+
+    Some(MipsInfo {
+        model: model.into(),
+        cpu: processor_from_model(model),
+        isa: field("isa").and_then(isa_from_list),
+        ases: field("ASEs implemented")
+            .unwrap_or("")
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "mips32r6",
+    target_arch = "mips64r6"
+))]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match super::mips_info()
+        .ok()
+        .and_then(|info| info.cpu.or(info.isa))
+    {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips64",
+    target_arch = "mips32r6",
+    target_arch = "mips64r6"
+))]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match super::mips_info().ok().and_then(|info| info.isa) {
+        Some(isa) => isa.into(),
+        None => x.machine.clone(),
+    };
+}
@@ -0,0 +1,143 @@
+use crate::cpu::{Endianness, PowerAbi, PowerInfo};
+
+// `AT_PLATFORM` values (as set by the kernel's cputable), and the name LLVM uses for the processor
+const PLATFORM_PROCESSORS: &[(&str, &str)] = &[
+    ("power4", "pwr4"),
+    ("power5", "pwr5"),
+    ("power5+", "pwr5x"),
+    ("power6", "pwr6"),
+    ("power6x", "pwr6x"),
+    ("power7", "pwr7"),
+    ("power7+", "pwr7"),
+    ("power8", "pwr8"),
+    ("power9", "pwr9"),
+    ("power10", "pwr10"),
+    ("power11", "pwr11"),
+    ("ppc970", "970"),
+    ("ppc440", "440"),
+    ("ppc440gp", "440"),
+    ("ppce500mc", "e500mc"),
+    ("ppce5500", "e5500"),
+    ("ppce6500", "e6500"),
+];
+
+pub fn processor_from_platform(platform: &str) -> Option<&'static str> {
+    PLATFORM_PROCESSORS
+        .iter()
+        .find(|(plat, _)| *plat == platform)
+        .map(|&(_, name)| name)
+}
+
+const PPC_FEATURE_POWER5_PLUS: u64 = 0x0002_0000;
+const PPC_FEATURE_ARCH_2_05: u64 = 0x0000_1000;
+const PPC_FEATURE_ARCH_2_06: u64 = 0x0000_0100;
+
+const PPC_FEATURE2_ARCH_2_07: u64 = 0x8000_0000;
+const PPC_FEATURE2_ARCH_3_00: u64 = 0x0080_0000;
+const PPC_FEATURE2_ARCH_3_1: u64 = 0x0004_0000;
+
+/// Determines the latest version of the Power ISA reported by `AT_HWCAP` and `AT_HWCAP2`
+pub fn isa_from_hwcap(hwcap: u64, hwcap2: u64) -> Option<(u8, u8)> {
+    if hwcap2 & PPC_FEATURE2_ARCH_3_1 != 0 {
+        Some((3, 1))
+    } else if hwcap2 & PPC_FEATURE2_ARCH_3_00 != 0 {
+        Some((3, 0))
+    } else if hwcap2 & PPC_FEATURE2_ARCH_2_07 != 0 {
+        Some((2, 7))
+    } else if hwcap & PPC_FEATURE_ARCH_2_06 != 0 {
+        Some((2, 6))
+    } else if hwcap & PPC_FEATURE_ARCH_2_05 != 0 {
+        Some((2, 5))
+    } else if hwcap & PPC_FEATURE_POWER5_PLUS != 0 {
+        Some((2, 3))
+    } else {
+        None
+    }
+}
+
+const EM_PPC: u16 = 20;
+const EM_PPC64: u16 = 21;
+
+const EF_PPC64_ABI: u32 = 0x3;
+
+/// Reads the byte order and (on 64-bit) the ELF ABI version from a PowerPC ELF header
+pub fn abi_from_elf_header(header: &[u8]) -> Option<(Endianness, Option<PowerAbi>)> {
+    if header.len() < 20 || header[..4] != *b"\x7fELF" {
+        return None;
+    }
+
+    let endianness = match header[5] {
+        1 => Endianness::Little,
+        2 => Endianness::Big,
+        _ => return None,
+    };
+
+    let read_u16 = |off: usize| {
+        let bytes = [header[off], header[off + 1]];
+        match endianness {
+            Endianness::Little => u16::from_le_bytes(bytes),
+            Endianness::Big => u16::from_be_bytes(bytes),
+        }
+    };
+
+    match (header[4], read_u16(18)) {
+        // ELFCLASS32 has no ELFv2
+        (1, EM_PPC) => Some((endianness, None)),
+        (2, EM_PPC64) => {
+            let flags = header.get(48..52)?;
+            let flags = [flags[0], flags[1], flags[2], flags[3]];
+            let flags = match endianness {
+                Endianness::Little => u32::from_le_bytes(flags),
+                Endianness::Big => u32::from_be_bytes(flags),
+            };
+
+            // Files that don't specify follow the convention for their byte order
+            let abi = match (flags & EF_PPC64_ABI, endianness) {
+                (1, _) | (0, Endianness::Big) => PowerAbi::ElfV1,
+                _ => PowerAbi::ElfV2,
+            };
+
+            Some((endianness, Some(abi)))
+        }
+        _ => None,
+    }
+}
+
+pub fn decode(
+    platform: &str,
+    base_platform: Option<&str>,
+    hwcap: u64,
+    hwcap2: u64,
+    header: Option<&[u8]>,
+) -> PowerInfo {
+    let abi = header.and_then(abi_from_elf_header);
+
+    PowerInfo {
+        cpu: processor_from_platform(platform),
+        platform: platform.into(),
+        base_platform: base_platform.map(Into::into),
+        isa: isa_from_hwcap(hwcap, hwcap2),
+        endianness: match abi {
+            Some((endianness, _)) => endianness,
+            None if cfg!(target_endian = "little") => Endianness::Little,
+            None => Endianness::Big,
+        },
+        abi: abi.and_then(|(_, abi)| abi),
+    }
+}
+
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match super::power_info().ok().and_then(|info| info.cpu) {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match super::power_info() {
+        Ok(info) => info.hardware_platform(&x.machine),
+        Err(_) => x.machine.clone(),
+    };
+}
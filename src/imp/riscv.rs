@@ -0,0 +1,347 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::cpu::{RiscvIsa, RiscvProfile};
+
+// The canonical order of single-letter extensions, which also orders multi-letter `Z` extensions by their second letter
+const CANONICAL_ORDER: &str = "imafdqlcbkjtpvh";
+
+fn canonical_index(c: char) -> usize {
+    CANONICAL_ORDER.find(c).unwrap_or(CANONICAL_ORDER.len())
+}
+
+/// Orders extensions as they appear in a canonical ISA string: single-letter extensions first,
+/// then `Z` extensions by category, then `S` and `X` extensions
+fn canonical_key(ext: &str) -> (usize, usize, &str) {
+    let mut chars = ext.chars();
+    let first = chars.next().unwrap_or('x');
+
+    match first {
+        _ if ext.len() == 1 => (0, canonical_index(first), ext),
+        'z' => (1, canonical_index(chars.next().unwrap_or('z')), ext),
+        's' => (2, 0, ext),
+        _ => (3, 0, ext),
+    }
+}
+
+/// Removes an explicit version suffix, such as `2p0`, from a multi-letter extension
+fn strip_version(ext: &str) -> &str {
+    let Some((name, minor)) = ext.rsplit_once('p') else {
+        return ext;
+    };
+
+    let major = name.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    if !minor.is_empty()
+        && minor.bytes().all(|b| b.is_ascii_digit())
+        && major.len() < name.len()
+        && !major.is_empty()
+    {
+        major
+    } else {
+        ext
+    }
+}
+
+fn push_ext(extensions: &mut Vec<String>, ext: &str) {
+    if !extensions.iter().any(|e| e == ext) {
+        extensions.push(ext.into());
+    }
+}
+
+pub fn parse_isa_string(isa: &str) -> Option<RiscvIsa> {
+    let isa = isa.trim().to_ascii_lowercase();
+    let rest = isa.strip_prefix("rv")?;
+
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let xlen = rest[..digits].parse::<u32>().ok()?;
+    let rest = &rest[digits..];
+
+    let mut extensions = Vec::new();
+    let mut segments = rest.split('_');
+
+    // The first segment holds the base ISA and the single-letter extensions, each optionally followed by a version like `2p1`
+    let single = segments.next().unwrap_or("");
+    let mut chars = single.char_indices().peekable();
+    let mut multi = None;
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            'z' | 's' | 'x' => {
+                multi = Some(&single[idx..]);
+                break;
+            }
+            'g' => {
+                for ext in ["i", "m", "a", "f", "d", "zicsr", "zifencei"] {
+                    push_ext(&mut extensions, ext);
+                }
+            }
+            'e' => push_ext(&mut extensions, "e"),
+            c if c.is_ascii_lowercase() => {
+                let mut buf = [0; 4];
+                push_ext(&mut extensions, c.encode_utf8(&mut buf));
+            }
+            _ => continue,
+        }
+
+        while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+
+        let mut lookahead = chars.clone();
+        if lookahead.next().is_some_and(|(_, c)| c == 'p')
+            && lookahead.peek().is_some_and(|(_, c)| c.is_ascii_digit())
+        {
+            chars.next();
+            while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+        }
+    }
+
+    for ext in multi.into_iter().chain(segments) {
+        let ext = strip_version(ext);
+        if !ext.is_empty() {
+            push_ext(&mut extensions, ext);
+        }
+    }
+
+    extensions.sort_by(|a, b| canonical_key(a).cmp(&canonical_key(b)));
+
+    Some(RiscvIsa { xlen, extensions })
+}
+
+/// Finds the first `isa` line of `/proc/cpuinfo`, which lists the extensions usable by userspace
+pub fn isa_from_cpuinfo(cpuinfo: &str) -> Option<RiscvIsa> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "isa")
+            .then(|| parse_isa_string(value))
+            .flatten()
+    })
+}
+
+const RVA20U64: &[&str] = &["i", "m", "a", "f", "d", "c"];
+
+const RVA22U64: &[&str] = &[
+    "zba",
+    "zbb",
+    "zbs",
+    "zicbom",
+    "zicboz",
+    "zihintpause",
+    "zfhmin",
+    "zkt",
+];
+
+// `supm` is left out, since it depends on kernel support for pointer masking rather than on the processor
+const RVA23U64: &[&str] = &[
+    "v",
+    "zvfhmin",
+    "zvbb",
+    "zvkt",
+    "zihintntl",
+    "zicond",
+    "zimop",
+    "zcmop",
+    "zcb",
+    "zfa",
+    "zawrs",
+];
+
+// Only the extensions that Linux reports are required, since counters, cache-block prefetch, and memory model
+// guarantees (such as `zicntr`, `zicbop`, and `ziccrse`) are not reported by older kernels
+pub fn profile(isa: &RiscvIsa) -> Option<RiscvProfile> {
+    if isa.xlen != 64 {
+        return None;
+    }
+
+    let levels = [
+        (RiscvProfile::Rva20, RVA20U64),
+        (RiscvProfile::Rva22, RVA22U64),
+        (RiscvProfile::Rva23, RVA23U64),
+    ];
+
+    levels
+        .into_iter()
+        .take_while(|(_, exts)| exts.iter().all(|ext| isa.contains(ext)))
+        .map(|(profile, _)| profile)
+        .last()
+}
+
+// The bit of `RISCV_HWPROBE_KEY_BASE_BEHAVIOR` reporting the IMA base ISA, from `asm/hwprobe.h`
+const HWPROBE_BASE_BEHAVIOR_IMA: u64 = 1 << 0;
+
+// The bits of `RISCV_HWPROBE_KEY_IMA_EXT_0`, from `asm/hwprobe.h`
+const HWPROBE_IMA_EXT_0: &[(u32, &[&str])] = &[
+    (0, &["f", "d"]),
+    (1, &["c"]),
+    (2, &["v"]),
+    (3, &["zba"]),
+    (4, &["zbb"]),
+    (5, &["zbs"]),
+    (6, &["zicboz"]),
+    (7, &["zbc"]),
+    (8, &["zbkb"]),
+    (9, &["zbkc"]),
+    (10, &["zbkx"]),
+    (11, &["zknd"]),
+    (12, &["zkne"]),
+    (13, &["zknh"]),
+    (14, &["zksed"]),
+    (15, &["zksh"]),
+    (16, &["zkt"]),
+    (17, &["zvbb"]),
+    (18, &["zvbc"]),
+    (19, &["zvkb"]),
+    (20, &["zvkg"]),
+    (21, &["zvkned"]),
+    (22, &["zvknha"]),
+    (23, &["zvknhb"]),
+    (24, &["zvksed"]),
+    (25, &["zvksh"]),
+    (26, &["zvkt"]),
+    (27, &["zfh"]),
+    (28, &["zfhmin"]),
+    (29, &["zihintntl"]),
+    (30, &["zvfh"]),
+    (31, &["zvfhmin"]),
+    (32, &["zfa"]),
+    (33, &["ztso"]),
+    (34, &["zacas"]),
+    (35, &["zicond"]),
+    (36, &["zihintpause"]),
+    (37, &["zve32x"]),
+    (38, &["zve32f"]),
+    (39, &["zve64x"]),
+    (40, &["zve64f"]),
+    (41, &["zve64d"]),
+    (42, &["zimop"]),
+    (43, &["zca"]),
+    (44, &["zcb"]),
+    (45, &["zcd"]),
+    (46, &["zcf"]),
+    (47, &["zcmop"]),
+    (48, &["zawrs"]),
+    (49, &["supm"]),
+    (50, &["zicntr"]),
+    (51, &["zihpm"]),
+    (52, &["zfbfmin"]),
+    (53, &["zvfbfmin"]),
+    (54, &["zvfbfwma"]),
+    (55, &["zicbom"]),
+    (56, &["zaamo"]),
+    (57, &["zalrsc"]),
+    (58, &["zabha"]),
+];
+
+/// Builds the ISA from the `RISCV_HWPROBE_KEY_BASE_BEHAVIOR` and `RISCV_HWPROBE_KEY_IMA_EXT_0` values,
+/// adding the extensions listed by `/proc/cpuinfo` (if any) that `riscv_hwprobe` has no key for
+pub fn isa_from_hwprobe(base_behavior: u64, ima_ext_0: u64, cpuinfo: Option<RiscvIsa>) -> RiscvIsa {
+    let mut extensions = Vec::new();
+
+    if base_behavior & HWPROBE_BASE_BEHAVIOR_IMA != 0 {
+        for ext in ["i", "m", "a"] {
+            push_ext(&mut extensions, ext);
+        }
+    }
+
+    for &(bit, exts) in HWPROBE_IMA_EXT_0 {
+        if ima_ext_0 & (1 << bit) != 0 {
+            for ext in exts {
+                push_ext(&mut extensions, ext);
+            }
+        }
+    }
+
+    if let Some(cpuinfo) = &cpuinfo {
+        for ext in &cpuinfo.extensions {
+            push_ext(&mut extensions, ext);
+        }
+    }
+
+    extensions.sort_by(|a, b| canonical_key(a).cmp(&canonical_key(b)));
+
+    RiscvIsa {
+        xlen: cpuinfo.map_or(usize::BITS, |isa| isa.xlen),
+        extensions,
+    }
+}
+
+// Processors by `mvendorid` and `marchid`, and `mimpid` where it matters (`None` matches any `mimpid`)
+const PROCESSOR_IDS: &[(u64, u64, Option<u64>, &str)] = &[
+    // Open-source architecture IDs, assigned by RISC-V International
+    (0, 1, None, "rocket-rv64"),
+    (0, 25, None, "xiangshan-nanhu"),
+    // SiFive
+    (0x489, 0x8000_0000_0000_0007, None, "sifive-u74"),
+    (0x489, 0x8000_0000_0000_0008, None, "sifive-p550"),
+    // SpacemiT
+    (0x710, 0x8000_0000_5800_0001, None, "spacemit-x60"),
+];
+
+// Processors by the first device tree `compatible` string of the cpu node, which Linux reports as `uarch` in `/proc/cpuinfo`
+const PROCESSOR_COMPATIBLES: &[(&str, &str)] = &[
+    ("eswin,eic770x", "sifive-p550"),
+    ("sifive,bullet0", "sifive-u74"),
+    ("sifive,p450", "sifive-p450"),
+    ("sifive,p470", "sifive-p470"),
+    ("sifive,p550", "sifive-p550"),
+    ("sifive,p670", "sifive-p670"),
+    ("sifive,rocket0", "sifive-u54"),
+    ("sifive,u54", "sifive-u54"),
+    ("sifive,u54-mc", "sifive-u54"),
+    ("sifive,u74", "sifive-u74"),
+    ("sifive,u74-mc", "sifive-u74"),
+    ("sifive,x280", "sifive-x280"),
+    ("spacemit,x60", "spacemit-x60"),
+    ("thead,c906", "thead-c906"),
+];
+
+pub fn processor_from_ids(mvendorid: u64, marchid: u64, mimpid: u64) -> Option<&'static str> {
+    PROCESSOR_IDS
+        .iter()
+        .find(|&&(vendor, arch, imp, _)| {
+            vendor == mvendorid && arch == marchid && imp.is_none_or(|imp| imp == mimpid)
+        })
+        .map(|&(_, _, _, name)| name)
+}
+
+pub fn processor_from_compatible(compatible: &str) -> Option<&'static str> {
+    PROCESSOR_COMPATIBLES
+        .iter()
+        .find(|(compat, _)| *compat == compatible)
+        .map(|&(_, name)| name)
+}
+
+/// Names the processor from the `mvendorid`, `marchid` and `mimpid` lines of `/proc/cpuinfo`, or else from its `uarch` line
+pub fn processor_from_cpuinfo(cpuinfo: &str) -> Option<&'static str> {
+    let field = |name: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim())
+        })
+    };
+
+    let hex = |name: &str| u64::from_str_radix(field(name)?.trim_start_matches("0x"), 16).ok();
+
+    let by_id = match (hex("mvendorid"), hex("marchid"), hex("mimpid")) {
+        (Some(mvendorid), Some(marchid), mimpid) => {
+            processor_from_ids(mvendorid, marchid, mimpid.unwrap_or(0))
+        }
+        _ => None,
+    };
+
+    by_id.or_else(|| processor_from_compatible(field("uarch")?))
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match super::riscv_processor() {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match super::riscv_isa().ok().and_then(|isa| isa.profile()) {
+        Some(profile) => profile.as_str().into(),
+        None => x.machine.clone(),
+    };
+}
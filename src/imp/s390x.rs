@@ -0,0 +1,115 @@
+use crate::cpu::S390xInfo;
+
+// Machine types, and the architecture level they implement
+const MACHINE_LEVELS: &[(u16, u8)] = &[
+    (2064, 5),  // z900
+    (2066, 5),  // z800
+    (2084, 6),  // z990
+    (2086, 6),  // z890
+    (2094, 7),  // z9 EC
+    (2096, 7),  // z9 BC
+    (2097, 8),  // z10 EC
+    (2098, 8),  // z10 BC
+    (2817, 9),  // z196
+    (2818, 9),  // z114
+    (2827, 10), // zEC12
+    (2828, 10), // zBC12
+    (2964, 11), // z13
+    (2965, 11), // z13s
+    (3906, 12), // z14
+    (3907, 12), // z14 ZR1
+    (8561, 13), // z15 T01
+    (8562, 13), // z15 T02
+    (3931, 14), // z16 A01
+    (3932, 14), // z16 A02
+    (9175, 15), // z17 ME1
+];
+
+pub fn machine_arch_level(machine_type: u16) -> Option<u8> {
+    MACHINE_LEVELS
+        .iter()
+        .find(|(ty, _)| *ty == machine_type)
+        .map(|&(_, level)| level)
+}
+
+// The name each architecture level is best known by, starting from arch5
+const LEVEL_NAMES: &[&str] = &[
+    "z900", "z990", "z9-ec", "z10", "z196", "zEC12", "z13", "z14", "z15", "z16", "z17",
+];
+
+pub fn processor_from_level(level: u8) -> Option<&'static str> {
+    LEVEL_NAMES.get(usize::from(level).checked_sub(5)?).copied()
+}
+
+const HWCAP_S390_VXRS: u64 = 1 << 11;
+const HWCAP_S390_VXRS_EXT: u64 = 1 << 13;
+const HWCAP_S390_VXRS_EXT2: u64 = 1 << 15;
+const HWCAP_S390_NNPA: u64 = 1 << 20;
+
+/// Finds the machine type from the `machine = 3931` field of a `processor N:` line (or the `machine : 3931` line of newer kernels)
+fn machine_type_from_cpuinfo(cpuinfo: &str) -> Option<u16> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim();
+
+        let machine = if key.starts_with("processor ") {
+            // Such as `version = 00,  identification = 0C5F28,  machine = 3931`
+            value.split(',').find_map(|field| {
+                let (key, value) = field.split_once('=')?;
+                (key.trim() == "machine").then_some(value)
+            })?
+        } else if key == "machine" {
+            value
+        } else {
+            return None;
+        };
+
+        machine.trim().parse().ok()
+    })
+}
+
+pub fn decode(cpuinfo: &str, hwcap: u64) -> S390xInfo {
+    let machine_type = machine_type_from_cpuinfo(cpuinfo);
+
+    // The vector facilities are only usable if the kernel enables them, which caps the level userspace can use
+    let facility_level = if hwcap & HWCAP_S390_NNPA != 0 {
+        Some(14)
+    } else if hwcap & HWCAP_S390_VXRS_EXT2 != 0 {
+        Some(13)
+    } else if hwcap & HWCAP_S390_VXRS_EXT != 0 {
+        Some(12)
+    } else if hwcap & HWCAP_S390_VXRS != 0 {
+        Some(11)
+    } else {
+        None
+    };
+
+    let arch_level = match (machine_type.and_then(machine_arch_level), facility_level) {
+        // Later machines don't necessarily add a facility that Linux reports, so the machine is trusted beyond arch14
+        (Some(machine), Some(14)) => Some(machine),
+        (Some(machine), Some(facility)) => Some(machine.min(facility)),
+        (Some(machine), None) => Some(machine.min(10)),
+        (None, facility) => facility,
+    };
+
+    S390xInfo {
+        machine_type,
+        arch_level,
+    }
+}
+
+#[cfg(target_arch = "s390x")]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match super::s390x_info().ok().and_then(|info| info.processor()) {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(target_arch = "s390x")]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match super::s390x_info().ok().and_then(|info| info.arch_level) {
+        Some(level) => alloc::format!("arch{level}"),
+        None => x.machine.clone(),
+    };
+}
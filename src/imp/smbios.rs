@@ -0,0 +1,115 @@
+use alloc::string::String;
+
+use crate::dmi::{ChassisType, Dmi};
+
+const TYPE_BIOS: u8 = 0;
+const TYPE_SYSTEM: u8 = 1;
+const TYPE_BASEBOARD: u8 = 2;
+const TYPE_CHASSIS: u8 = 3;
+const TYPE_END: u8 = 127;
+
+/// A single structure, with its formatted area (including the 4 byte header) and its strings
+struct Structure<'a> {
+    formatted: &'a [u8],
+    strings: &'a [u8],
+}
+
+impl Structure<'_> {
+    fn byte(&self, offset: usize) -> Option<u8> {
+        self.formatted.get(offset).copied()
+    }
+
+    /// Looks up the string whose (1-based) number is at `offset` in the formatted area
+    fn string(&self, offset: usize) -> Option<String> {
+        let index = usize::from(self.byte(offset)?).checked_sub(1)?;
+
+        let s = self.strings.split(|&b| b == 0).nth(index)?;
+        let s = String::from_utf8_lossy(s);
+        let s = s.trim();
+
+        (!s.is_empty()).then(|| s.into())
+    }
+}
+
+fn structures(mut table: &[u8]) -> impl Iterator<Item = Structure<'_>> {
+    core::iter::from_fn(move || {
+        let &[ty, len, ..] = table else {
+            return None;
+        };
+
+        let len = usize::from(len);
+
+        if ty == TYPE_END || len < 4 || len > table.len() {
+            return None;
+        }
+
+        let (formatted, rest) = table.split_at(len);
+
+        // The string-set ends with two NULs (and is just two NULs if there are no strings)
+        let end = rest.windows(2).position(|w| w == [0, 0])?;
+        let strings = &rest[..end];
+        table = &rest[end + 2..];
+
+        Some(Structure { formatted, strings })
+    })
+}
+
+pub fn decode_table(table: &[u8]) -> Dmi {
+    let mut dmi = Dmi::default();
+
+    for s in structures(table) {
+        // Only the first structure of each type describes the system as a whole
+        match s.formatted[0] {
+            TYPE_BIOS if dmi.bios_vendor.is_none() && dmi.bios_version.is_none() => {
+                dmi.bios_vendor = s.string(0x04);
+                dmi.bios_version = s.string(0x05);
+                dmi.bios_date = s.string(0x08);
+            }
+            TYPE_SYSTEM if dmi.sys_vendor.is_none() && dmi.product_name.is_none() => {
+                dmi.sys_vendor = s.string(0x04);
+                dmi.product_name = s.string(0x05);
+                dmi.product_version = s.string(0x06);
+                dmi.product_serial = s.string(0x07);
+            }
+            TYPE_BASEBOARD if dmi.board_vendor.is_none() && dmi.board_name.is_none() => {
+                dmi.board_vendor = s.string(0x04);
+                dmi.board_name = s.string(0x05);
+                dmi.board_version = s.string(0x06);
+                dmi.board_serial = s.string(0x07);
+            }
+            TYPE_CHASSIS if dmi.chassis_type.is_none() => {
+                dmi.chassis_type = s.byte(0x05).and_then(ChassisType::from_code);
+            }
+            _ => {}
+        }
+    }
+
+    dmi
+}
+
+/// Fills in each field of `dmi` that is not known from `raw`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn merge(dmi: &mut Dmi, raw: Dmi) {
+    macro_rules! merge {
+        ($($field:ident),*) => {
+            $(if dmi.$field.is_none() {
+                dmi.$field = raw.$field;
+            })*
+        };
+    }
+
+    merge!(
+        sys_vendor,
+        product_name,
+        product_version,
+        product_serial,
+        board_vendor,
+        board_name,
+        board_version,
+        board_serial,
+        bios_vendor,
+        bios_version,
+        bios_date,
+        chassis_type
+    );
+}
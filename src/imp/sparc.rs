@@ -0,0 +1,68 @@
+use crate::cpu::SparcInfo;
+
+// Processors, by a substring of the `cpu` line, in the order they are checked
+const MODEL_SUBSTRINGS: &[(&str, &str)] = &[
+    ("Niagara2", "niagara2"),
+    ("Niagara3", "niagara3"),
+    ("Niagara4", "niagara4"),
+    ("Niagara5", "niagara4"),
+    ("Niagara", "niagara"),
+    ("SPARC-M7", "niagara7"),
+    ("SPARC-S7", "niagara7"),
+    ("SPARC-M8", "m8"),
+    ("UltraSparc T1", "niagara"),
+    ("UltraSparc T2", "niagara2"),
+    ("UltraSparc T3", "niagara3"),
+    ("UltraSparc T4", "niagara4"),
+    ("UltraSparc T5", "niagara4"),
+    ("UltraSparc III", "ultrasparc3"),
+    ("UltraSparc IV", "ultrasparc3"),
+    ("UltraSparc", "ultrasparc"),
+    ("SPARC64", "v9"),
+];
+
+pub fn processor_from_model(model: &str, machine_type: Option<&str>) -> Option<&'static str> {
+    MODEL_SUBSTRINGS
+        .iter()
+        .find(|(substring, _)| model.contains(substring))
+        .map(|&(_, name)| name)
+        .or(match machine_type {
+            Some("sun4u" | "sun4v") => Some("v9"),
+            Some("sun4m" | "sun4d") => Some("v8"),
+            _ => None,
+        })
+}
+
+pub fn decode_cpuinfo(cpuinfo: &str) -> Option<SparcInfo> {
+    let field = |name: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim())
+        })
+    };
+
+    let model = field("cpu")?;
+    let machine_type = field("type");
+
+    Some(SparcInfo {
+        model: model.into(),
+        cpu: processor_from_model(model, machine_type),
+        machine_type: machine_type.map(Into::into),
+    })
+}
+
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+pub fn populate_processor(x: &mut crate::Uname) {
+    x.processor = match super::sparc_info().ok().and_then(|info| info.cpu) {
+        Some(name) => name.into(),
+        None => x.machine.clone(),
+    };
+}
+
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+pub fn populate_hardware_platform(x: &mut crate::Uname) {
+    x.hardware_platform = match super::sparc_info().ok().and_then(|info| info.machine_type) {
+        Some(machine_type) => machine_type,
+        None => x.machine.clone(),
+    };
+}
@@ -1,10 +1,15 @@
-use crate::{Uname, helper::bytes_to_string};
+use crate::{
+    Memory, Uname,
+    helper::{bytes_to_string, errno},
+};
+
+pub const UNSUPPORTED: i32 = libc::ENOSYS;
 
 pub fn populate_uname(v: &mut Uname) -> Result<(), i32> {
     let mut name: libc::utsname = unsafe { core::mem::zeroed() };
 
     if unsafe { libc::uname(&mut name) } < 0 {
-        return Err(unsafe { errno_sys::errno_location().read() });
+        return Err(errno());
     }
 
     v.kernel_name = bytes_to_string(&name.sysname);
@@ -19,3 +24,23 @@ pub fn populate_uname(v: &mut Uname) -> Result<(), i32> {
 
     Ok(())
 }
+
+pub fn os_memory() -> Result<Memory, i32> {
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+    if page_size < 0 {
+        return Err(errno());
+    }
+
+    let pages = unsafe { libc::sysconf(libc::_SC_PHYS_PAGES) };
+
+    if pages < 0 {
+        return Err(errno());
+    }
+
+    Ok(Memory {
+        total: (pages as u64) * (page_size as u64),
+        page_size: page_size as u64,
+        ..Memory::default()
+    })
+}
@@ -0,0 +1,159 @@
+use crate::{dmi::Dmi, virt::Virtualization};
+
+// Hypervisor vendor signatures in cpuid leaf 0x4000_0000
+const CPUID_SIGNATURES: &[(&[u8; 12], Virtualization)] = &[
+    (b"KVMKVMKVM\0\0\0", Virtualization::Kvm),
+    (b"Linux KVM Hv", Virtualization::Kvm),
+    (b"TCGTCGTCGTCG", Virtualization::Qemu),
+    (b"XenVMMXenVMM", Virtualization::XenHvm),
+    (b"Microsoft Hv", Virtualization::Microsoft),
+    (b"VMwareVMware", Virtualization::VMware),
+    (b"VBoxVBoxVBox", Virtualization::VirtualBox),
+    (b"prl hyperv  ", Virtualization::Parallels),
+    (b" lrpepyh  vr", Virtualization::Parallels),
+    (b"bhyve bhyve ", Virtualization::Bhyve),
+    (b"ACRNACRNACRN", Virtualization::Acrn),
+    (b"QNXQVMBSQG\0\0", Virtualization::Qnx),
+];
+
+pub fn from_cpuid_signature(signature: [u8; 12]) -> Virtualization {
+    CPUID_SIGNATURES
+        .iter()
+        .find(|(sig, _)| **sig == signature)
+        .map_or(Virtualization::Other, |&(_, virt)| virt)
+}
+
+// Prefixes of the DMI vendor and product strings that identify a hypervisor
+const DMI_VENDORS: &[(&str, Virtualization)] = &[
+    ("KVM", Virtualization::Kvm),
+    ("OpenStack", Virtualization::Kvm),
+    ("KubeVirt", Virtualization::Kvm),
+    ("Amazon EC2", Virtualization::Amazon),
+    ("Google Compute Engine", Virtualization::Google),
+    ("Firecracker", Virtualization::Firecracker),
+    ("QEMU", Virtualization::Qemu),
+    ("VMware", Virtualization::VMware),
+    ("VMW", Virtualization::VMware),
+    ("innotek GmbH", Virtualization::VirtualBox),
+    ("VirtualBox", Virtualization::VirtualBox),
+    ("Xen", Virtualization::XenHvm),
+    ("Bochs", Virtualization::Bochs),
+    ("Parallels", Virtualization::Parallels),
+    ("BHYVE", Virtualization::Bhyve),
+    ("Hyper-V", Virtualization::Microsoft),
+    ("Apple Virtualization", Virtualization::Apple),
+];
+
+pub fn from_dmi(dmi: &Dmi) -> Option<Virtualization> {
+    // Hyper-V reports itself as Microsoft's "Virtual Machine", but Microsoft also makes physical Surface devices
+    if dmi.sys_vendor.as_deref() == Some("Microsoft Corporation")
+        && dmi.product_name.as_deref() == Some("Virtual Machine")
+    {
+        return Some(Virtualization::Microsoft);
+    }
+
+    [
+        &dmi.product_name,
+        &dmi.sys_vendor,
+        &dmi.board_vendor,
+        &dmi.bios_vendor,
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|s| {
+        DMI_VENDORS
+            .iter()
+            .find(|(prefix, _)| s.starts_with(prefix))
+            .map(|&(_, virt)| virt)
+    })
+}
+
+/// Combines the hypervisor reported by cpuid with the one named in the DMI strings
+///
+/// `has_cpuid` is whether `cpuid` comes from cpuid (on x86), where `None` means the hypervisor bit is clear
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn combine(
+    has_cpuid: bool,
+    cpuid: Option<Virtualization>,
+    dmi: Option<Virtualization>,
+) -> Option<Virtualization> {
+    match (cpuid, dmi) {
+        // Many hypervisors are built on KVM, and only identify themselves in DMI.
+        // VirtualBox can also offer a KVM interface to guests
+        (
+            Some(Virtualization::Kvm),
+            Some(
+                virt @ (Virtualization::Amazon
+                | Virtualization::Google
+                | Virtualization::Firecracker
+                | Virtualization::VirtualBox),
+            ),
+        ) => Some(virt),
+        // Other hypervisors offer the Hyper-V interface to Windows guests
+        (Some(Virtualization::Microsoft), Some(virt)) => Some(virt),
+        (Some(virt), _) => Some(virt),
+        // Bare-metal EC2 and Compute Engine instances have the same DMI strings as virtual machines
+        (None, Some(Virtualization::Amazon | Virtualization::Google)) if has_cpuid => None,
+        (None, virt) => virt,
+    }
+}
+
+/// Identifies the hypervisor from the `compatible` property of the device tree's `/hypervisor` node
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn from_device_tree_hypervisor(compatible: &[u8]) -> Option<Virtualization> {
+    compatible
+        .split(|&b| b == 0)
+        .find_map(|compatible| match compatible {
+            b"linux,kvm" => Some(Virtualization::Kvm),
+            b"xen,xen" => Some(Virtualization::XenHvm),
+            b"vmware" => Some(Virtualization::VMware),
+            _ => None,
+        })
+}
+
+/// Identifies the hypervisor of an IBM Z system from the `Control Program` lines of `/proc/sysinfo`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn from_s390_sysinfo(sysinfo: &str) -> Option<Virtualization> {
+    sysinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| {
+            key.trim_start_matches(|c: char| c.is_ascii_alphanumeric())
+                .trim()
+                == "Control Program"
+        })
+        .find_map(|(_, value)| {
+            let value = value.trim();
+            if value.starts_with("z/VM") {
+                Some(Virtualization::Zvm)
+            } else if value.starts_with("KVM") {
+                Some(Virtualization::Kvm)
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_cpuid_and_dmi() {
+        use Virtualization::{Amazon, Google, Kvm, Microsoft, Qemu, VMware};
+
+        assert_eq!(combine(true, Some(Kvm), Some(Amazon)), Some(Amazon));
+        assert_eq!(combine(true, Some(Kvm), Some(Qemu)), Some(Kvm));
+        assert_eq!(combine(true, Some(Microsoft), Some(Qemu)), Some(Qemu));
+        assert_eq!(combine(true, Some(VMware), None), Some(VMware));
+        assert_eq!(combine(true, None, None), None);
+
+        // EC2 .metal instances
+        assert_eq!(combine(true, None, Some(Amazon)), None);
+        assert_eq!(combine(true, None, Some(Google)), None);
+        assert_eq!(combine(true, None, Some(VMware)), Some(VMware));
+
+        // Without cpuid, only DMI is available
+        assert_eq!(combine(false, None, Some(Amazon)), Some(Amazon));
+    }
+}
@@ -5,7 +5,7 @@ use alloc::{
     vec::Vec,
 };
 
-use crate::Uname;
+use crate::{Memory, Uname};
 
 use windows::Win32::{
     Foundation::ERROR_MORE_DATA,
@@ -22,6 +22,9 @@ use windows::Win32::{
 
 use windows::Wdk::System::SystemServices::RtlGetVersion;
 
+/// `HRESULT_FROM_WIN32(ERROR_NOT_SUPPORTED)`
+pub const UNSUPPORTED: i32 = 0x8007_0032_u32 as i32;
+
 pub fn populate_uname(x: &mut Uname) -> Result<(), i32> {
     let mut vstr = Vec::with_capacity(System::WindowsProgramming::MAX_COMPUTERNAME_LENGTH as usize);
 
@@ -138,3 +141,30 @@ pub fn populate_uname(x: &mut Uname) -> Result<(), i32> {
 
     Ok(())
 }
+
+pub fn os_memory() -> Result<Memory, i32> {
+    let mut status: System::SystemInformation::MEMORYSTATUSEX = unsafe { mem::zeroed() };
+    status.dwLength = core::mem::size_of::<System::SystemInformation::MEMORYSTATUSEX>() as u32;
+
+    if let Err(e) = unsafe { System::SystemInformation::GlobalMemoryStatusEx(&mut status) } {
+        return Err(e.code().0);
+    }
+
+    let mut sysinfo = unsafe { mem::zeroed() };
+
+    unsafe { System::SystemInformation::GetSystemInfo(&mut sysinfo) };
+
+    let large_page = unsafe { System::Memory::GetLargePageMinimum() };
+
+    Ok(Memory {
+        total: status.ullTotalPhys,
+        available: Some(status.ullAvailPhys),
+        page_size: u64::from(sysinfo.dwPageSize),
+        huge_page_sizes: if large_page != 0 {
+            alloc::vec![large_page as u64]
+        } else {
+            Vec::new()
+        },
+        cgroup_limit: None,
+    })
+}
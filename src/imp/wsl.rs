@@ -0,0 +1,59 @@
+use crate::virt::{Wsl, WslVersion};
+
+/// Recognizes the kernel release of WSL, such as `4.4.0-19041-Microsoft` (WSL1, which includes the Windows build) or `5.15.153.1-microsoft-standard-WSL2`
+pub fn parse_kernel_release(release: &str) -> Option<Wsl> {
+    let release = release.trim();
+
+    // WSL1 emulates Linux in the Windows kernel, and the release is `4.4.0-<build>-Microsoft`
+    if let Some(rest) = release.strip_suffix("-Microsoft") {
+        return Some(Wsl {
+            version: WslVersion::Wsl1,
+            windows_build: rest.rsplit('-').next().and_then(|build| build.parse().ok()),
+            interop: false,
+            distro_name: None,
+        });
+    }
+
+    // WSL2 runs a real Linux kernel, built by Microsoft as `microsoft-standard` (and `microsoft-standard-WSL2` since 5.10)
+    if release.contains("microsoft") || release.ends_with("-WSL2") {
+        return Some(Wsl {
+            version: WslVersion::Wsl2,
+            windows_build: None,
+            interop: false,
+            distro_name: None,
+        });
+    }
+
+    None
+}
+
+#[cfg(unix)]
+pub fn detect(root: &str) -> Option<Wsl> {
+    use alloc::string::String;
+
+    let root = root.trim_end_matches('/');
+
+    let read = |path: &str| {
+        let path = alloc::ffi::CString::new(alloc::format!("{root}{path}")).ok()?;
+        crate::helper::read_file(&path).ok()
+    };
+    let read_string = |path: &str| read(path).map(|v| String::from_utf8_lossy(&v).into_owned());
+
+    let mut wsl = parse_kernel_release(&read_string("/proc/sys/kernel/osrelease")?)?;
+
+    // Recent versions of WSL register the interpreter late in boot, under a different name
+    wsl.interop = [
+        "/proc/sys/fs/binfmt_misc/WSLInterop",
+        "/proc/sys/fs/binfmt_misc/WSLInterop-late",
+    ]
+    .iter()
+    .filter_map(|path| read_string(path))
+    .any(|entry| entry.lines().next() == Some("enabled"));
+
+    wsl.distro_name = read("/proc/self/environ").and_then(|environ| {
+        let name = super::container::env_var(&environ, "WSL_DISTRO_NAME")?;
+        Some(String::from_utf8_lossy(name).into_owned())
+    });
+
+    Some(wsl)
+}
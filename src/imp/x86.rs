@@ -1,20 +1,62 @@
 #[cfg(target_arch = "x86_64")]
-use core::arch::x86_64::__cpuid;
+use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv, CpuidResult};
 
 #[cfg(target_arch = "x86")]
-use core::arch::x86::__cpuid;
+use core::arch::x86::{__cpuid, __cpuid_count, _xgetbv, CpuidResult};
 
-use alloc::string::ToString;
+use alloc::{string::ToString, vec::Vec};
 
-use crate::Uname;
+use crate::{
+    Uname,
+    cpu::{Cache, CacheType, CpuFeatures, ProcessorMatch},
+};
 
 struct Version {
     family: u32,
     model: u32,
-    stepping: u8,
+}
+
+/// Checks whether the bits in `mask` can be toggled in EFLAGS, restoring the original EFLAGS afterwards
+#[cfg(target_arch = "x86")]
+fn eflags_toggles(mask: u32) -> bool {
+    let before: u32;
+    let after: u32;
+    unsafe {
+        core::arch::asm!(
+            "pushfd",
+            "pop {before}",
+            "mov {tmp}, {before}",
+            "xor {tmp}, {mask}",
+            "push {tmp}",
+            "popfd",
+            "pushfd",
+            "pop {after}",
+            "push {before}",
+            "popfd",
+            before = out(reg) before,
+            after = out(reg) after,
+            tmp = out(reg) _,
+            mask = in(reg) mask,
+        );
+    }
+
+    ((before ^ after) & mask) != 0
+}
+
+/// Checks whether the processor supports the cpuid instruction, which is only possible if EFLAGS.ID can be toggled
+#[cfg(target_arch = "x86")]
+fn has_cpuid() -> bool {
+    eflags_toggles(1 << 21)
 }
 
 fn get_version_info() -> Version {
+    // Without cpuid, a 486 can still be distinguished from a 386, because only a 486 can toggle EFLAGS.AC
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        let family = if eflags_toggles(1 << 18) { 4 } else { 3 };
+        return Version { family, model: 0 };
+    }
+
     let version_info = __cpuid(1).eax;
 
     let ext_family = (version_info >> 20) & 0xFF;
@@ -37,49 +79,86 @@ fn get_version_info() -> Version {
         raw_family
     };
 
-    let stepping = (version_info & 0xF) as u8;
+    Version { family, model }
+}
 
-    Version {
-        family,
-        model,
-        stepping,
+pub fn populate_processor(x: &mut Uname) {
+    match identify_processor() {
+        Some((proc, _)) => x.processor = proc.to_string(),
+        // Fallback: Either ix86 or x86_64, depending on the current architecture
+        None => x.processor = x.machine.clone(),
     }
 }
 
-pub fn populate_processor(x: &mut Uname) {
+/// Reads the processor brand string from cpuid leaves 0x8000_0002 through 0x8000_0004, if supported
+fn brand_string() -> Option<[u8; 48]> {
+    if __cpuid(0x8000_0000).eax < 0x8000_0004 {
+        return None;
+    }
+
+    let words: [[u32; 4]; 3] = core::array::from_fn(|i| {
+        let leaf = __cpuid(0x8000_0002 + i as u32);
+        [leaf.eax, leaf.ebx, leaf.ecx, leaf.edx]
+    });
+
+    Some(bytemuck::cast(words))
+}
+
+fn brand_contains(needle: &[u8]) -> bool {
+    brand_string().is_some_and(|brand| brand.windows(needle.len()).any(|w| w == needle))
+}
+
+/// Identifies the host processor, either exactly from the cpuid family and model, or inferred from the supported features.
+pub fn identify_processor() -> Option<(&'static str, ProcessorMatch)> {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return match get_version_info().family {
+            3 => Some(("i386", ProcessorMatch::Exact)),
+            _ => Some(("i486", ProcessorMatch::Exact)),
+        };
+    }
+
     let name = __cpuid(0);
 
     let name = [name.ebx, name.edx, name.ecx];
 
     let name: [u8; 12] = bytemuck::cast(name);
 
-    let Version {
-        family,
-        model,
-        stepping,
-    } = get_version_info();
+    let Version { family, model, .. } = get_version_info();
+
+    let long_mode =
+        __cpuid(0x8000_0000).eax >= 0x8000_0001 && (__cpuid(0x8000_0001).edx & (1 << 29)) != 0;
 
     let proc = match (&name, family, model) {
         (b"GenuineIntel", 4, _) => "i486",
-        (b"GenuineIntel", 5, 0..=2 | 7 | 9) => "pentium",
+        (b"GenuineIntel", 5, 0..=2 | 7) => "pentium",
+        (b"GenuineIntel", 5, 9) => "lakemont",
         (b"GenuineIntel", 5, 4 | 8) => "pentium-mmx",
         (b"GenuineIntel", 6, 1) => "pentiumpro",
         (b"GenuineIntel", 6, 3 | 5 | 6) => "pentium2",
         (b"GenuineIntel", 6, 7 | 8 | 10 | 11) => "pentium3",
         (b"GenuineIntel", 6, 9 | 13 | 21) => "pentium-m",
-        (b"GenuineIntel", 6, 23 | 29) => "core2",
+        (b"GenuineIntel", 6, 14) => "yonah",
+        (b"GenuineIntel", 6, 15 | 22) => "core2",
+        (b"GenuineIntel", 6, 23 | 29) => "penryn",
         (b"GenuineIntel", 6, 26 | 30 | 31 | 46) => "nehalem",
-        (b"GenuineIntel", 6, 28 | 38) => "bonnel",
+        (b"GenuineIntel", 6, 28 | 38 | 39 | 53 | 54) => "bonnell",
         (b"GenuineIntel", 6, 37 | 44 | 47) => "westmere",
         (b"GenuineIntel", 6, 42 | 45) => "sandybridge",
-        (b"GenuineIntel", 6, 54 | 55 | 77 | 90 | 93) => "silvermont",
+        (b"GenuineIntel", 6, 55 | 74 | 76 | 77 | 90 | 93) => "silvermont",
         (b"GenuineIntel", 6, 58 | 62) => "ivybridge",
         (b"GenuineIntel", 6, 60 | 63 | 69 | 70) => "haswell",
         (b"GenuineIntel", 6, 61 | 71 | 79 | 86) => "broadwell",
-        (b"GenuineIntel", 6, 78 | 94 | 142 | 158) => "skylake",
-        (b"GenuineIntel", 6, 85) if stepping == 7 => "cascadelake",
-        (b"GenuineIntel", 6, 85) if stepping == 1 => "cooperlake",
+        (b"GenuineIntel", 6, 78 | 94 | 142 | 158 | 165 | 166) => "skylake",
+        (b"GenuineIntel", 6, 85) if cpu_features().contains(CpuFeatures::AVX512BF16) => {
+            "cooperlake"
+        }
+        (b"GenuineIntel", 6, 85) if cpu_features().contains(CpuFeatures::AVX512VNNI) => {
+            "cascadelake"
+        }
         (b"GenuineIntel", 6, 85) => "skylake-avx512",
+        (b"GenuineIntel", 6, 87) => "knl",
+        (b"GenuineIntel", 6, 133) => "knm",
         (b"GenuineIntel", 6, 92 | 95) => "goldmont",
         (b"GenuineIntel", 6, 102) => "cannonlake",
         (b"GenuineIntel", 6, 122) => "goldmont-plus",
@@ -97,7 +176,10 @@ pub fn populate_processor(x: &mut Uname) {
         (b"GenuineIntel", 6, 174) => "graniterapids-d",
         (b"GenuineIntel", 6, 175) => "sierraforest",
         (b"GenuineIntel", 6, 181 | 197) => "arrowlake",
-        (b"GenuineIntel", 6, 183 | 186 | 190 | 160) => "raptorlake",
+        (b"GenuineIntel", 6, 183 | 186 | 191 | 160) => "raptorlake",
+        // Alder Lake-N and Twin Lake only have Gracemont E-cores
+        (b"GenuineIntel", 6, 190) => "gracemont",
+        (b"GenuineIntel", 6, 182) => "grandridge",
         (b"GenuineIntel", 6, 198) => "arrowlake-s",
         (b"GenuineIntel", 6, 188 | 189) => "lunarlake",
         (b"GenuineIntel", 6, 204) => "pantherlake",
@@ -106,8 +188,8 @@ pub fn populate_processor(x: &mut Uname) {
         (b"GenuineIntel", 6, 221) => "clearwaterforest",
 
         (b"GenuineIntel", 0x0F, 0..=2) => "pentium4",
-        (b"GenuineIntel", 0x0F, 3 | 4) => "prescott",
-        (b"GenuineIntel", 0x0F, 6) => "pentium4m",
+        (b"GenuineIntel", 0x0F, 3 | 4 | 6) if long_mode => "nocona",
+        (b"GenuineIntel", 0x0F, 3 | 4 | 6) => "prescott",
         (b"GenuineIntel", 18, 1) => "novalake",
         (b"GenuineIntel", 19, 1) => "diamondrapids",
         (b"AuthenticAMD", 4, _) => "i486",
@@ -115,11 +197,27 @@ pub fn populate_processor(x: &mut Uname) {
         (b"AuthenticAMD", 5, 6 | 7) => "k6",
         (b"AuthenticAMD", 5, 8) => "k6-2",
         (b"AuthenticAMD", 5, 9 | 13) => "k6-3",
-        (b"AuthenticAMD", 6, 0..5) => "athlon",
+        (b"AuthenticAMD", 5, 10) => "geode",
+        (b"AuthenticAMD", 6, 4) => "athlon-tbird",
+        (b"AuthenticAMD", 6, _) if !cpu_features().contains(CpuFeatures::SSE) => "athlon",
+        (b"AuthenticAMD", 6, _) if brand_contains(b"MP") => "athlon-mp",
+        (b"AuthenticAMD", 6, _) if brand_contains(b"XP") => "athlon-xp",
         (b"AuthenticAMD", 6, _) => "athlon-4",
-        // TODO: It may be important to divide this into athlon64, athlon-fx, and optron (plus sse3 counterparts)
-        (b"AuthenticAMD", 15, ..32) => "k8",
-        (b"AuthenticAMD", 15, _) => "k8-sse3",
+        // K8 can only be told apart by the brand string
+        (b"AuthenticAMD", 15, _) => {
+            let sse3 = cpu_features().contains(CpuFeatures::SSE3);
+            if brand_contains(b"Opteron") {
+                if sse3 { "opteron-sse3" } else { "opteron" }
+            } else if brand_contains(b"Athlon") && brand_contains(b"FX") {
+                "athlon-fx"
+            } else if brand_contains(b"Athlon") && brand_contains(b"64") {
+                if sse3 { "athlon64-sse3" } else { "athlon64" }
+            } else if sse3 {
+                "k8-sse3"
+            } else {
+                "k8"
+            }
+        }
         // Yes, there are more than just 10h in this list... but apparently there's nothing until Family 15h
         (b"AuthenticAMD", 16 | 18, _) => "amdfam10",
         (b"AuthenticAMD", 20, _) => "btver1",
@@ -134,13 +232,149 @@ pub fn populate_processor(x: &mut Uname) {
         (b"AuthenticAMD", 25, _) => "znver4",
         (b"AuthenticAMD", 26, ..0x50 | 0x60..0x80) => "znver5",
         (b"AuthenticAMD", 26, _) => "znver6",
+        // Hygon Dhyana and its successors are licensed Zen 1 cores
+        (b"HygonGenuine", 24, _) => "znver1",
+        (b"  Shanghai  " | b"CentaurHauls", 7, 0x1b | 0x3b) => "lujiazui",
+        (b"  Shanghai  " | b"CentaurHauls", 7, 0x5b) => "yongfeng",
+        (b"  Shanghai  " | b"CentaurHauls", 7, 0x6b) => "shijidadao",
+        (b"CentaurHauls", 5, 4) => "winchip-c6",
+        (b"CentaurHauls", 5, 8 | 9) => "winchip2",
+        (b"CentaurHauls", 6, 6..=8) => "c3",
+        (b"CentaurHauls", 6, 9) => "c3-2",
+        (b"CentaurHauls", 6, 10 | 13) => "c7",
+        (b"CentaurHauls", 6, 15) if cpu_features().contains(CpuFeatures::AVX2) => "eden-x4",
+        (b"CentaurHauls", 6, 15) if cpu_features().contains(CpuFeatures::SSE4_1) => "nano-3000",
+        (b"CentaurHauls", 6, 15) => "nano",
+        (b"Geode by NSC", 5, _) => "geode",
+        (b"Vortex86 SoC", 5, _) => "i586",
+        (b"Vortex86 SoC", 6, _) => "i686",
+        (b"CyrixInstead", 4, _) => "i486",
+        (b"CyrixInstead", 5, _) => "i586",
+        (b"CyrixInstead", 6, _) => "i686",
+        (b"GenuineTMx86" | b"TransmetaCPU", 5, _) => "i586",
+        (b"GenuineTMx86" | b"TransmetaCPU", 15, _) => "i686",
         _ => {
-            // Fallback: Either ix86 or x86_64, depending on the current architecture
-            &x.machine
+            return infer_processor(&name, cpu_features())
+                .map(|proc| (proc, ProcessorMatch::Inferred));
         }
     };
 
-    x.processor = proc.to_string();
+    Some((proc, ProcessorMatch::Exact))
+}
+
+// Ordered from newest to oldest, so that the first entry whose features are all supported is the closest match
+const INTEL_INFERENCE: &[(CpuFeatures, &str)] = &[
+    (CpuFeatures::APXF, "diamondrapids"),
+    (CpuFeatures::AVX10_2, "diamondrapids"),
+    (CpuFeatures::AMX_COMPLEX, "graniterapids-d"),
+    (CpuFeatures::AMX_FP16, "graniterapids"),
+    (CpuFeatures::AVX10_1, "graniterapids"),
+    (CpuFeatures::AMX_TILE, "sapphirerapids"),
+    (CpuFeatures::AVX512VP2INTERSECT, "tigerlake"),
+    (CpuFeatures::AVX512VBMI2, "icelake-client"),
+    (CpuFeatures::AVX512VBMI, "cannonlake"),
+    (CpuFeatures::AVX512BF16, "cooperlake"),
+    (CpuFeatures::AVX512VNNI, "cascadelake"),
+    (
+        CpuFeatures::AVX512F
+            .union(CpuFeatures::AVX512CD)
+            .union(CpuFeatures::AVX512BW)
+            .union(CpuFeatures::AVX512DQ)
+            .union(CpuFeatures::AVX512VL),
+        "skylake-avx512",
+    ),
+    (
+        CpuFeatures::SHA512
+            .union(CpuFeatures::SM3)
+            .union(CpuFeatures::SM4)
+            .union(CpuFeatures::AVX2),
+        "arrowlake-s",
+    ),
+    (
+        CpuFeatures::AVXVNNIINT8
+            .union(CpuFeatures::AVXIFMA)
+            .union(CpuFeatures::AVXNECONVERT)
+            .union(CpuFeatures::AVX2),
+        "sierraforest",
+    ),
+    (CpuFeatures::AVXVNNI.union(CpuFeatures::AVX2), "alderlake"),
+    (CpuFeatures::XSAVEC.union(CpuFeatures::AVX2), "skylake"),
+    (CpuFeatures::ADX.union(CpuFeatures::AVX2), "broadwell"),
+    (
+        CpuFeatures::AVX2
+            .union(CpuFeatures::BMI1)
+            .union(CpuFeatures::BMI2)
+            .union(CpuFeatures::FMA),
+        "haswell",
+    ),
+    (
+        CpuFeatures::AVX
+            .union(CpuFeatures::F16C)
+            .union(CpuFeatures::RDRAND),
+        "ivybridge",
+    ),
+    (CpuFeatures::AVX, "sandybridge"),
+    (CpuFeatures::GFNI.union(CpuFeatures::SSE4_2), "tremont"),
+    (CpuFeatures::SHA.union(CpuFeatures::SSE4_2), "goldmont"),
+    (CpuFeatures::MOVBE.union(CpuFeatures::SSE4_2), "silvermont"),
+    (CpuFeatures::SSE4_2.union(CpuFeatures::POPCNT), "nehalem"),
+    (CpuFeatures::SSE4_1, "penryn"),
+    (CpuFeatures::MOVBE.union(CpuFeatures::SSSE3), "bonnell"),
+    (CpuFeatures::SSSE3, "core2"),
+    (CpuFeatures::SSE3.union(CpuFeatures::CMPXCHG16B), "nocona"),
+    (CpuFeatures::SSE3, "prescott"),
+    (CpuFeatures::SSE2, "pentium4"),
+    (CpuFeatures::SSE, "pentium3"),
+];
+
+const AMD_INFERENCE: &[(CpuFeatures, &str)] = &[
+    (
+        CpuFeatures::AVX512VP2INTERSECT.union(CpuFeatures::AVX512F),
+        "znver5",
+    ),
+    (CpuFeatures::AVX512F, "znver4"),
+    (
+        CpuFeatures::VAES
+            .union(CpuFeatures::VPCLMULQDQ)
+            .union(CpuFeatures::AVX2),
+        "znver3",
+    ),
+    (CpuFeatures::SHA.union(CpuFeatures::AVX2), "znver1"),
+    (CpuFeatures::XOP.union(CpuFeatures::AVX2), "bdver4"),
+    (CpuFeatures::XOP.union(CpuFeatures::XSAVEOPT), "bdver3"),
+    (
+        CpuFeatures::XOP
+            .union(CpuFeatures::F16C)
+            .union(CpuFeatures::BMI1),
+        "bdver2",
+    ),
+    (CpuFeatures::XOP, "bdver1"),
+    (
+        CpuFeatures::AVX
+            .union(CpuFeatures::MOVBE)
+            .union(CpuFeatures::BMI1),
+        "btver2",
+    ),
+    (CpuFeatures::SSE4A.union(CpuFeatures::SSSE3), "btver1"),
+    (CpuFeatures::SSE4A, "amdfam10"),
+    (CpuFeatures::SSE3.union(CpuFeatures::LAHFSAHF), "k8-sse3"),
+    (CpuFeatures::SSE2, "k8"),
+    (CpuFeatures::SSE, "athlon-4"),
+];
+
+/// Infers the closest known processor from the supported features, for processors that are not known by family and model.
+fn infer_processor(vendor: &[u8; 12], features: CpuFeatures) -> Option<&'static str> {
+    let table = match vendor {
+        b"GenuineIntel" => INTEL_INFERENCE,
+        b"AuthenticAMD" | b"HygonGenuine" => AMD_INFERENCE,
+        // The feature sets of other vendors' processors don't follow either table
+        _ => return None,
+    };
+
+    table
+        .iter()
+        .find(|&&(required, _)| features.contains(required))
+        .map(|&(_, proc)| proc)
 }
 
 #[cfg(target_arch = "x86")]
@@ -157,3 +391,500 @@ pub fn populate_hardware_platform(x: &mut Uname) {
         }
     };
 }
+
+/// Executes cpuid for `leaf`/`subleaf` if `present`, and otherwise returns all zeroes.
+fn cpuid_if(present: bool, leaf: u32, subleaf: u32) -> CpuidResult {
+    if present {
+        __cpuid_count(leaf, subleaf)
+    } else {
+        CpuidResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        }
+    }
+}
+
+const LEAF_1_ECX: usize = 0;
+const LEAF_1_EDX: usize = 1;
+const LEAF_7_0_EBX: usize = 2;
+const LEAF_7_0_ECX: usize = 3;
+const LEAF_7_0_EDX: usize = 4;
+const LEAF_7_1_EAX: usize = 5;
+const LEAF_7_1_EDX: usize = 6;
+const LEAF_D_1_EAX: usize = 7;
+const LEAF_19_EBX: usize = 8;
+const LEAF_1E_1_EAX: usize = 9;
+const EXT_LEAF_1_ECX: usize = 10;
+
+const FEATURE_BITS: &[(usize, u32, CpuFeatures)] = &[
+    (LEAF_1_ECX, 0, CpuFeatures::SSE3),
+    (LEAF_1_ECX, 1, CpuFeatures::PCLMULQDQ),
+    (LEAF_1_ECX, 9, CpuFeatures::SSSE3),
+    (LEAF_1_ECX, 12, CpuFeatures::FMA),
+    (LEAF_1_ECX, 13, CpuFeatures::CMPXCHG16B),
+    (LEAF_1_ECX, 19, CpuFeatures::SSE4_1),
+    (LEAF_1_ECX, 20, CpuFeatures::SSE4_2),
+    (LEAF_1_ECX, 22, CpuFeatures::MOVBE),
+    (LEAF_1_ECX, 23, CpuFeatures::POPCNT),
+    (LEAF_1_ECX, 25, CpuFeatures::AES),
+    (LEAF_1_ECX, 26, CpuFeatures::XSAVE),
+    (LEAF_1_ECX, 28, CpuFeatures::AVX),
+    (LEAF_1_ECX, 29, CpuFeatures::F16C),
+    (LEAF_1_ECX, 30, CpuFeatures::RDRAND),
+    (LEAF_1_EDX, 0, CpuFeatures::X87),
+    (LEAF_1_EDX, 24, CpuFeatures::FXSR),
+    (LEAF_1_EDX, 25, CpuFeatures::SSE),
+    (LEAF_1_EDX, 26, CpuFeatures::SSE2),
+    (LEAF_7_0_EBX, 3, CpuFeatures::BMI1),
+    (LEAF_7_0_EBX, 5, CpuFeatures::AVX2),
+    (LEAF_7_0_EBX, 8, CpuFeatures::BMI2),
+    (LEAF_7_0_EBX, 9, CpuFeatures::ERMSB),
+    (LEAF_7_0_EBX, 11, CpuFeatures::RTM),
+    (LEAF_7_0_EBX, 16, CpuFeatures::AVX512F),
+    (LEAF_7_0_EBX, 17, CpuFeatures::AVX512DQ),
+    (LEAF_7_0_EBX, 18, CpuFeatures::RDSEED),
+    (LEAF_7_0_EBX, 19, CpuFeatures::ADX),
+    (LEAF_7_0_EBX, 21, CpuFeatures::AVX512IFMA),
+    (LEAF_7_0_EBX, 28, CpuFeatures::AVX512CD),
+    (LEAF_7_0_EBX, 29, CpuFeatures::SHA),
+    (LEAF_7_0_EBX, 30, CpuFeatures::AVX512BW),
+    (LEAF_7_0_EBX, 31, CpuFeatures::AVX512VL),
+    (LEAF_7_0_ECX, 1, CpuFeatures::AVX512VBMI),
+    (LEAF_7_0_ECX, 6, CpuFeatures::AVX512VBMI2),
+    (LEAF_7_0_ECX, 8, CpuFeatures::GFNI),
+    (LEAF_7_0_ECX, 9, CpuFeatures::VAES),
+    (LEAF_7_0_ECX, 10, CpuFeatures::VPCLMULQDQ),
+    (LEAF_7_0_ECX, 11, CpuFeatures::AVX512VNNI),
+    (LEAF_7_0_ECX, 12, CpuFeatures::AVX512BITALG),
+    (LEAF_7_0_ECX, 14, CpuFeatures::AVX512VPOPCNTDQ),
+    (LEAF_7_0_ECX, 23, CpuFeatures::KL),
+    (LEAF_7_0_EDX, 8, CpuFeatures::AVX512VP2INTERSECT),
+    (LEAF_7_0_EDX, 22, CpuFeatures::AMX_BF16),
+    (LEAF_7_0_EDX, 23, CpuFeatures::AVX512FP16),
+    (LEAF_7_0_EDX, 24, CpuFeatures::AMX_TILE),
+    (LEAF_7_0_EDX, 25, CpuFeatures::AMX_INT8),
+    (LEAF_7_1_EAX, 0, CpuFeatures::SHA512),
+    (LEAF_7_1_EAX, 1, CpuFeatures::SM3),
+    (LEAF_7_1_EAX, 2, CpuFeatures::SM4),
+    (LEAF_7_1_EAX, 4, CpuFeatures::AVXVNNI),
+    (LEAF_7_1_EAX, 5, CpuFeatures::AVX512BF16),
+    (LEAF_7_1_EAX, 21, CpuFeatures::AMX_FP16),
+    (LEAF_7_1_EAX, 23, CpuFeatures::AVXIFMA),
+    (LEAF_7_1_EAX, 31, CpuFeatures::MOVRS),
+    (LEAF_7_1_EDX, 4, CpuFeatures::AVXVNNIINT8),
+    (LEAF_7_1_EDX, 5, CpuFeatures::AVXNECONVERT),
+    (LEAF_7_1_EDX, 8, CpuFeatures::AMX_COMPLEX),
+    (LEAF_7_1_EDX, 10, CpuFeatures::AVXVNNIINT16),
+    (LEAF_7_1_EDX, 21, CpuFeatures::APXF),
+    (LEAF_D_1_EAX, 0, CpuFeatures::XSAVEOPT),
+    (LEAF_D_1_EAX, 1, CpuFeatures::XSAVEC),
+    (LEAF_D_1_EAX, 3, CpuFeatures::XSAVES),
+    (LEAF_19_EBX, 2, CpuFeatures::WIDEKL),
+    (LEAF_1E_1_EAX, 4, CpuFeatures::AMX_FP8),
+    (LEAF_1E_1_EAX, 6, CpuFeatures::AMX_TF32),
+    (LEAF_1E_1_EAX, 7, CpuFeatures::AMX_AVX512),
+    (LEAF_1E_1_EAX, 8, CpuFeatures::AMX_MOVRS),
+    (EXT_LEAF_1_ECX, 0, CpuFeatures::LAHFSAHF),
+    (EXT_LEAF_1_ECX, 5, CpuFeatures::LZCNT),
+    (EXT_LEAF_1_ECX, 6, CpuFeatures::SSE4A),
+    (EXT_LEAF_1_ECX, 8, CpuFeatures::PRFCHW),
+    (EXT_LEAF_1_ECX, 11, CpuFeatures::XOP),
+    (EXT_LEAF_1_ECX, 21, CpuFeatures::TBM),
+];
+
+// Features that use the YMM registers, and need XCR0.SSE and XCR0.AVX
+const YMM_FEATURES: CpuFeatures = CpuFeatures::AVX
+    .union(CpuFeatures::AVX2)
+    .union(CpuFeatures::FMA)
+    .union(CpuFeatures::F16C)
+    .union(CpuFeatures::VAES)
+    .union(CpuFeatures::VPCLMULQDQ)
+    .union(CpuFeatures::AVXIFMA)
+    .union(CpuFeatures::AVXNECONVERT)
+    .union(CpuFeatures::AVXVNNI)
+    .union(CpuFeatures::AVXVNNIINT8)
+    .union(CpuFeatures::AVXVNNIINT16)
+    .union(CpuFeatures::SHA512)
+    .union(CpuFeatures::SM3)
+    .union(CpuFeatures::SM4)
+    .union(ZMM_FEATURES);
+
+// Features that use the ZMM and opmask registers, and additionally need XCR0.opmask, XCR0.ZMM_Hi256, and XCR0.Hi16_ZMM
+const ZMM_FEATURES: CpuFeatures = CpuFeatures::AVX512F
+    .union(CpuFeatures::AVX512BF16)
+    .union(CpuFeatures::AVX512BITALG)
+    .union(CpuFeatures::AVX512BW)
+    .union(CpuFeatures::AVX512CD)
+    .union(CpuFeatures::AVX512DQ)
+    .union(CpuFeatures::AVX512FP16)
+    .union(CpuFeatures::AVX512IFMA)
+    .union(CpuFeatures::AVX512VBMI)
+    .union(CpuFeatures::AVX512VBMI2)
+    .union(CpuFeatures::AVX512VL)
+    .union(CpuFeatures::AVX512VNNI)
+    .union(CpuFeatures::AVX512VP2INTERSECT)
+    .union(CpuFeatures::AVX512VPOPCNTDQ)
+    .union(CpuFeatures::AVX10_1)
+    .union(CpuFeatures::AVX10_2)
+    .union(CpuFeatures::AMX_AVX512);
+
+// Features that use the tile registers, and need XCR0.XTILECFG and XCR0.XTILEDATA
+const AMX_FEATURES: CpuFeatures = CpuFeatures::AMX_AVX512
+    .union(CpuFeatures::AMX_BF16)
+    .union(CpuFeatures::AMX_COMPLEX)
+    .union(CpuFeatures::AMX_FP16)
+    .union(CpuFeatures::AMX_FP8)
+    .union(CpuFeatures::AMX_INT8)
+    .union(CpuFeatures::AMX_MOVRS)
+    .union(CpuFeatures::AMX_TF32)
+    .union(CpuFeatures::AMX_TILE);
+
+const XCR0_YMM: u64 = 0b110;
+const XCR0_ZMM: u64 = 0b1110_0000;
+const XCR0_AMX: u64 = 0b11 << 17;
+const XCR0_APX: u64 = 1 << 19;
+
+pub fn cpu_features() -> CpuFeatures {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return CpuFeatures::empty();
+    }
+
+    let max_leaf = __cpuid(0).eax;
+    let max_ext_leaf = __cpuid(0x8000_0000).eax;
+
+    let leaf_1 = cpuid_if(max_leaf >= 0x01, 0x01, 0);
+    let leaf_7_0 = cpuid_if(max_leaf >= 0x07, 0x07, 0);
+    let leaf_7_1 = cpuid_if(max_leaf >= 0x07 && leaf_7_0.eax >= 1, 0x07, 1);
+    let leaf_d_1 = cpuid_if(max_leaf >= 0x0D, 0x0D, 1);
+    let leaf_19 = cpuid_if(max_leaf >= 0x19, 0x19, 0);
+    let leaf_1e_0 = cpuid_if(max_leaf >= 0x1E, 0x1E, 0);
+    let leaf_1e_1 = cpuid_if(max_leaf >= 0x1E && leaf_1e_0.eax >= 1, 0x1E, 1);
+    let ext_leaf_1 = cpuid_if(max_ext_leaf >= 0x8000_0001, 0x8000_0001, 0);
+
+    let words = [
+        leaf_1.ecx,
+        leaf_1.edx,
+        leaf_7_0.ebx,
+        leaf_7_0.ecx,
+        leaf_7_0.edx,
+        leaf_7_1.eax,
+        leaf_7_1.edx,
+        leaf_d_1.eax,
+        leaf_19.ebx,
+        leaf_1e_1.eax,
+        ext_leaf_1.ecx,
+    ];
+
+    let mut features = CpuFeatures::empty();
+
+    for &(word, bit, flag) in FEATURE_BITS {
+        if (words[word] & (1 << bit)) != 0 {
+            features |= flag;
+        }
+    }
+
+    // AVX10 reports its version in leaf 0x24, rather than as individual bits
+    if (leaf_7_1.edx & (1 << 19)) != 0 {
+        match cpuid_if(max_leaf >= 0x24, 0x24, 0).ebx & 0xFF {
+            0 => {}
+            1 => features |= CpuFeatures::AVX10_1,
+            _ => features |= CpuFeatures::AVX10_1 | CpuFeatures::AVX10_2,
+        }
+    }
+
+    // OSXSAVE
+    let xcr0 = if (leaf_1.ecx & (1 << 27)) != 0 {
+        // SAFETY: CPUID.01H:ECX.OSXSAVE indicates that XGETBV is supported and enabled by the OS
+        unsafe { _xgetbv(0) }
+    } else {
+        0
+    };
+
+    if (xcr0 & XCR0_YMM) != XCR0_YMM {
+        features &= !YMM_FEATURES;
+    }
+
+    if (xcr0 & XCR0_ZMM) != XCR0_ZMM {
+        features &= !ZMM_FEATURES;
+    }
+
+    if (xcr0 & XCR0_AMX) != XCR0_AMX {
+        features &= !AMX_FEATURES;
+    }
+
+    if (xcr0 & XCR0_APX) != XCR0_APX {
+        features &= !CpuFeatures::APXF;
+    }
+
+    features
+}
+
+/// Decodes cpuid leaf 0x04 (Intel) or 0x8000_001D (AMD), which share the same layout
+fn deterministic_caches(leaf: u32) -> Vec<Cache> {
+    let mut caches = Vec::new();
+
+    // A processor (or hypervisor) that never reports the end of the list would otherwise be queried forever
+    for subleaf in 0..16 {
+        let res = __cpuid_count(leaf, subleaf);
+
+        let cache_type = match res.eax & 0x1F {
+            0 => break,
+            1 => CacheType::Data,
+            2 => CacheType::Instruction,
+            _ => CacheType::Unified,
+        };
+
+        let line_size = (res.ebx & 0xFFF) + 1;
+        let partitions = ((res.ebx >> 12) & 0x3FF) + 1;
+        let ways = ((res.ebx >> 22) & 0x3FF) + 1;
+        let sets = res.ecx + 1;
+
+        caches.push(Cache {
+            level: ((res.eax >> 5) & 0x7) as u8,
+            cache_type,
+            size: u64::from(line_size) * u64::from(partitions) * u64::from(ways) * u64::from(sets),
+            line_size,
+            associativity: if (res.eax & (1 << 9)) != 0 {
+                None
+            } else {
+                Some(ways)
+            },
+            shared_by: ((res.eax >> 14) & 0xFFF) + 1,
+        });
+    }
+
+    caches
+}
+
+/// Decodes the associativity field of cpuid leaf 0x8000_0006
+///
+/// Other values are reserved (or, on Zen, 0x9 refers to leaf 0x8000_001D), and are not known.
+fn legacy_amd_associativity(field: u32) -> Option<u32> {
+    match field {
+        0x1 => Some(1),
+        0x2 => Some(2),
+        0x4 => Some(4),
+        0x6 => Some(8),
+        0x8 => Some(16),
+        0xA => Some(32),
+        0xB => Some(48),
+        0xC => Some(64),
+        0xD => Some(96),
+        0xE => Some(128),
+        _ => None,
+    }
+}
+
+/// Decodes the AMD leaves 0x8000_0005 and 0x8000_0006, used by processors that do not support leaf 0x8000_001D
+fn legacy_amd_caches(max_ext_leaf: u32) -> Vec<Cache> {
+    let mut caches = Vec::new();
+
+    if max_ext_leaf >= 0x8000_0005 {
+        let l1 = __cpuid(0x8000_0005);
+
+        for (reg, cache_type) in [(l1.ecx, CacheType::Data), (l1.edx, CacheType::Instruction)] {
+            let ways = (reg >> 16) & 0xFF;
+            caches.push(Cache {
+                level: 1,
+                cache_type,
+                size: u64::from(reg >> 24) * 1024,
+                line_size: reg & 0xFF,
+                // 0xFF is fully associative, and 0 is reserved
+                associativity: if ways == 0xFF || ways == 0 {
+                    None
+                } else {
+                    Some(ways)
+                },
+                shared_by: 1,
+            });
+        }
+    }
+
+    if max_ext_leaf >= 0x8000_0006 {
+        let l2_l3 = __cpuid(0x8000_0006);
+
+        if (l2_l3.ecx >> 12) & 0xF != 0 {
+            caches.push(Cache {
+                level: 2,
+                cache_type: CacheType::Unified,
+                size: u64::from(l2_l3.ecx >> 16) * 1024,
+                line_size: l2_l3.ecx & 0xFF,
+                associativity: legacy_amd_associativity((l2_l3.ecx >> 12) & 0xF),
+                shared_by: 1,
+            });
+        }
+
+        if (l2_l3.edx >> 12) & 0xF != 0 {
+            caches.push(Cache {
+                level: 3,
+                cache_type: CacheType::Unified,
+                size: u64::from(l2_l3.edx >> 18) * 512 * 1024,
+                line_size: l2_l3.edx & 0xFF,
+                associativity: legacy_amd_associativity((l2_l3.edx >> 12) & 0xF),
+                shared_by: 0,
+            });
+        }
+    }
+
+    caches
+}
+
+pub fn caches() -> Vec<Cache> {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return Vec::new();
+    }
+
+    let vendor = __cpuid(0);
+    let max_leaf = vendor.eax;
+    let vendor: [u8; 12] = bytemuck::cast([vendor.ebx, vendor.edx, vendor.ecx]);
+    let max_ext_leaf = __cpuid(0x8000_0000).eax;
+
+    match &vendor {
+        b"AuthenticAMD" | b"HygonGenuine" => {
+            // TopologyExtensions
+            if max_ext_leaf >= 0x8000_001D && (__cpuid(0x8000_0001).ecx & (1 << 22)) != 0 {
+                deterministic_caches(0x8000_001D)
+            } else {
+                legacy_amd_caches(max_ext_leaf)
+            }
+        }
+        _ if max_leaf >= 0x04 => deterministic_caches(0x04),
+        _ => Vec::new(),
+    }
+}
+
+/// Checks whether the processor mixes different kinds of cores (CPUID.07H.0H:EDX.Hybrid)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn is_hybrid() -> bool {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return false;
+    }
+
+    __cpuid(0).eax >= 0x07 && (__cpuid_count(0x07, 0).edx & (1 << 15)) != 0
+}
+
+/// Determines the kind of core the current logical processor belongs to, from cpuid leaf 0x1A
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn current_core_type() -> crate::cpu::CoreType {
+    use crate::cpu::CoreType;
+
+    if !is_hybrid() {
+        return CoreType::Performance;
+    }
+
+    if __cpuid(0).eax < 0x1A {
+        return CoreType::Unknown;
+    }
+
+    match __cpuid_count(0x1A, 0).eax >> 24 {
+        0x20 => CoreType::Efficiency,
+        0x40 => CoreType::Performance,
+        _ => CoreType::Unknown,
+    }
+}
+
+/// Returns the microarchitecture names of the performance and efficiency cores of a hybrid processor
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn hybrid_core_names() -> Option<(&'static str, &'static str)> {
+    if !is_hybrid() {
+        return None;
+    }
+
+    match identify_processor()? {
+        ("tremont", _) => Some(("sunnycove", "tremont")),
+        ("alderlake", _) => Some(("goldencove", "gracemont")),
+        ("raptorlake", _) => Some(("raptorcove", "gracemont")),
+        ("meteorlake", _) => Some(("redwoodcove", "crestmont")),
+        ("arrowlake" | "arrowlake-s" | "lunarlake", _) => Some(("lioncove", "skymont")),
+        ("pantherlake" | "wildcatlake", _) => Some(("cougarcove", "darkmont")),
+        ("novalake", _) => Some(("coyotecove", "arcticwolf")),
+        _ => None,
+    }
+}
+
+/// Reads the hypervisor vendor signature from cpuid leaf 0x4000_0000, if CPUID.01H:ECX reports that the processor is virtualized
+pub fn hypervisor_signature() -> Option<[u8; 12]> {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return None;
+    }
+
+    if __cpuid(0).eax < 1 || (__cpuid(1).ecx & (1 << 31)) == 0 {
+        return None;
+    }
+
+    let leaf = __cpuid(0x4000_0000);
+
+    Some(bytemuck::cast([leaf.ebx, leaf.ecx, leaf.edx]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_intel() {
+        let haswell = CpuFeatures::SSE4_2
+            | CpuFeatures::POPCNT
+            | CpuFeatures::AVX
+            | CpuFeatures::AVX2
+            | CpuFeatures::BMI1
+            | CpuFeatures::BMI2
+            | CpuFeatures::FMA;
+        assert_eq!(infer_processor(b"GenuineIntel", haswell), Some("haswell"));
+        assert_eq!(
+            infer_processor(b"GenuineIntel", haswell | CpuFeatures::ADX),
+            Some("broadwell")
+        );
+        assert_eq!(
+            infer_processor(b"GenuineIntel", haswell | CpuFeatures::AMX_TILE),
+            Some("sapphirerapids")
+        );
+        assert_eq!(
+            infer_processor(b"GenuineIntel", CpuFeatures::SSE4_1 | CpuFeatures::SSSE3),
+            Some("penryn")
+        );
+        assert_eq!(infer_processor(b"GenuineIntel", CpuFeatures::empty()), None);
+    }
+
+    #[test]
+    fn infer_amd() {
+        let zen = CpuFeatures::AVX2 | CpuFeatures::SHA | CpuFeatures::SSE4A;
+        assert_eq!(infer_processor(b"AuthenticAMD", zen), Some("znver1"));
+        assert_eq!(infer_processor(b"HygonGenuine", zen), Some("znver1"));
+        assert_eq!(
+            infer_processor(b"AuthenticAMD", zen | CpuFeatures::AVX512F),
+            Some("znver4")
+        );
+        assert_eq!(
+            infer_processor(b"AuthenticAMD", CpuFeatures::SSE4A | CpuFeatures::SSSE3),
+            Some("btver1")
+        );
+    }
+
+    #[test]
+    fn infer_unknown_vendor() {
+        let features = CpuFeatures::AVX2 | CpuFeatures::SHA | CpuFeatures::SSE4_2;
+        assert_eq!(infer_processor(b"  Shanghai  ", features), None);
+        assert_eq!(infer_processor(b"CentaurHauls", features), None);
+        assert_eq!(infer_processor(b"VIA VIA VIA ", features), None);
+    }
+
+    #[test]
+    fn amd_associativity() {
+        let ways = [1, 2, 4, 8, 16, 32, 48, 64, 96, 128];
+        let fields = [0x1, 0x2, 0x4, 0x6, 0x8, 0xA, 0xB, 0xC, 0xD, 0xE];
+        for (field, ways) in fields.into_iter().zip(ways) {
+            assert_eq!(legacy_amd_associativity(field), Some(ways));
+        }
+
+        for field in [0x0, 0x3, 0x5, 0x7, 0x9, 0xF] {
+            assert_eq!(legacy_amd_associativity(field), None);
+        }
+    }
+}
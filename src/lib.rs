@@ -6,7 +6,7 @@
 
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 mod helper;
 
@@ -28,6 +28,14 @@ pub struct Uname {
     pub machine: String,
     /// The Processor Name
     /// If known, this corresponds to a specific string that can be used with `-march`-like and `-mtune`-like flags in compilers to correspond closely with both feature support and timing information.
+    /// On x86, a processor which is not known by family and model is named after the closest known processor with the same features (see [`cpu::ProcessorMatch`]).
+    /// On Arm, the processor is named from the implementer and part number in its Main ID Register (see [`cpu::processor_from_midr`]).
+    /// On PowerPC, the processor is named from `AT_PLATFORM`, such as `pwr9` (see [`cpu::PowerInfo`]).
+    /// On IBM Z, the processor is named after the architecture level, such as `z16` (see [`cpu::S390xInfo`]).
+    /// On LoongArch, the processor is named from the processor identifier reported by `cpucfg`, such as `la464` (see [`cpu::LoongArchInfo`]).
+    /// On MIPS and SPARC Linux, the processor is named from the `cpu model` or `cpu` line of `/proc/cpuinfo`, such as `octeon3` or `niagara4`,
+    /// and MIPS processors which are not known are named by their ISA revision (see [`cpu::MipsInfo`] and [`cpu::SparcInfo`]).
+    /// On RISC-V, the processor is named from its `mvendorid`, `marchid` and `mimpid`, or its device tree `compatible` string (see [`cpu::riscv_processor_from_cpuinfo`]).
     /// Otherwise, it is the same as [`Uname::machine`]
     pub processor: String,
     /// The Hardware Platform.
@@ -35,8 +43,16 @@ pub struct Uname {
     /// This is the specific version of the [`Uname::machine`]. If not known for a given architecture, it is set to be the same as [`Uname::machine`]
     /// On x86_64, this corresponds to the microarchitecture level supported (according to cpuid).
     /// On x86-32 this is one of i386, i486, i586, i686, or i786 (depending on the cpu implemented).
-    /// Note that the default implementation for x86-32 (used other than on windows or lilium) will never report i386,
-    /// as the default implementation requires the processor to support the cpuid instruction
+    /// On processors without the cpuid instruction, the default implementation for x86-32 (used other than on windows or lilium)
+    /// distinguishes i386 from i486 by probing which bits of EFLAGS can be toggled.
+    /// On 32-bit Arm Linux, this is the architecture version from `AT_PLATFORM`, such as `armv6` or `armv7` (see [`cpu::ArmInfo`]).
+    /// On PowerPC Linux, this is the machine and the Power ISA version from `AT_HWCAP2`, such as `ppc64le-isa3.1`.
+    /// On IBM Z Linux, this is the architecture level, such as `arch14`.
+    /// On LoongArch, this is the architecture version supported according to `cpucfg`, such as `la64v1.0` or `la64v1.1`.
+    /// On MIPS Linux, this is the newest ISA revision, such as `mips32r2` or `mips64r6`. On SPARC Linux, this is the machine architecture, such as `sun4v`.
+    /// On RISC-V Linux, this is the most recent RVA profile supported, such as `rva22u64`, in the same form as on Lilium (see [`cpu::RiscvIsa::profile`]).
+    /// On AArch64 Linux, this is the architecture version inferred from `AT_HWCAP` and `AT_HWCAP2`, such as `armv8.2-a` or `armv9-a` (see [`cpu::Aarch64Features::architecture`]).
+    /// On AArch64 Lilium, this is the Armv8 architecture version reported by the kernel, such as `armv8.2-a`.
     ///
     ///
     /// This may be extended to support other architectures in the future
@@ -59,10 +75,40 @@ impl Uname {
     }
 }
 
+/// Describes the memory installed in the host, and the memory available to the current process.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Memory {
+    /// The total physical memory installed in the host, in bytes
+    pub total: u64,
+    /// The physical memory currently available for new allocations without swapping, in bytes, if known
+    pub available: Option<u64>,
+    /// The base page size, in bytes
+    pub page_size: u64,
+    /// The supported huge (or large) page sizes, in bytes, from smallest to largest
+    pub huge_page_sizes: Vec<u64>,
+    /// The memory limit imposed on the current process by its cgroup (such as by a container runtime), in bytes, if any
+    pub cgroup_limit: Option<u64>,
+}
+
+impl Memory {
+    /// Returns the amount of memory the current process can effectively use, which is the lesser of [`Memory::total`] and [`Memory::cgroup_limit`].
+    pub fn effective_limit(&self) -> u64 {
+        match self.cgroup_limit {
+            Some(limit) => limit.min(self.total),
+            None => self.total,
+        }
+    }
+}
+
 mod imp {
-    use alloc::string::ToString;
+    use alloc::{string::ToString, vec::Vec};
+    use error_repr::RawOsError;
 
-    use crate::Uname;
+    use crate::{
+        Memory, Uname,
+        cpu::{Cache, LogicalCpu, Topology},
+    };
 
     pub fn populate_os_name(x: &mut Uname) {
         x.sysname = core::env!("TARGET_OS").to_string();
@@ -72,6 +118,13 @@ mod imp {
         cfg_match::cfg_match! {
             target_arch = "x86_64" => x86_64::populate_hardware_platform(x),
             target_arch = "x86" => x86::populate_hardware_platform(x),
+            any(target_arch = "aarch64", target_arch = "arm") => arm::populate_hardware_platform(x),
+            any(target_arch = "riscv32", target_arch = "riscv64") => riscv::populate_hardware_platform(x),
+            any(target_arch = "powerpc", target_arch = "powerpc64") => powerpc::populate_hardware_platform(x),
+            target_arch = "s390x" => s390x::populate_hardware_platform(x),
+            target_arch = "loongarch64" => loongarch::populate_hardware_platform(x),
+            any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6") => mips::populate_hardware_platform(x),
+            any(target_arch = "sparc", target_arch = "sparc64") => sparc::populate_hardware_platform(x),
             _ => ({
                 x.hardware_platform = x.machine.clone();
             })
@@ -81,6 +134,13 @@ mod imp {
     pub fn populate_processor(x: &mut Uname) {
         cfg_match::cfg_match! {
             any(target_arch = "x86_64", target_arch = "x86") => x86::populate_processor(x),
+            any(target_arch = "aarch64", target_arch = "arm") => arm::populate_processor(x),
+            any(target_arch = "riscv32", target_arch = "riscv64") => riscv::populate_processor(x),
+            any(target_arch = "powerpc", target_arch = "powerpc64") => powerpc::populate_processor(x),
+            target_arch = "s390x" => s390x::populate_processor(x),
+            target_arch = "loongarch64" => loongarch::populate_processor(x),
+            any(target_arch = "mips", target_arch = "mips64", target_arch = "mips32r6", target_arch = "mips64r6") => mips::populate_processor(x),
+            any(target_arch = "sparc", target_arch = "sparc64") => sparc::populate_processor(x),
             _ => ({
                 x.processor = x.machine.clone();
             })
@@ -101,17 +161,211 @@ mod imp {
             pub use unix::*;
         }
         _ => {
-            pub fn populate_uname(_: &mut Uname) -> Result<(), error_repr::RawOsError> {
-                Err(0xDEADBEEF)
+            pub const UNSUPPORTED: RawOsError = 0xDEADBEEF;
+
+            pub fn populate_uname(_: &mut Uname) -> Result<(), RawOsError> {
+                Err(UNSUPPORTED)
             }
         }
     }
 
+    pub fn caches() -> Result<Vec<Cache>, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_arch = "x86_64", target_arch = "x86") => match x86::caches() {
+                caches if caches.is_empty() => os_caches(),
+                caches => Ok(caches),
+            },
+            _ => os_caches(),
+        }
+    }
+
+    fn os_caches() -> Result<Vec<Cache>, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::caches(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    pub fn aarch64_features() -> Option<crate::cpu::Aarch64Features> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => Some(linux::aarch64_features()),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    pub fn riscv_isa() -> Result<crate::cpu::RiscvIsa, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::riscv_isa(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    pub fn riscv_processor() -> Option<&'static str> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::riscv_processor(),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+    pub fn power_info() -> Result<crate::cpu::PowerInfo, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::power_info(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(target_arch = "s390x")]
+    pub fn s390x_info() -> Result<crate::cpu::S390xInfo, RawOsError> {
+        cfg_match::cfg_match! {
+            target_os = "linux" => linux::s390x_info(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "mips32r6",
+        target_arch = "mips64r6"
+    ))]
+    pub fn mips_info() -> Result<crate::cpu::MipsInfo, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::mips_info(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+    pub fn sparc_info() -> Result<crate::cpu::SparcInfo, RawOsError> {
+        cfg_match::cfg_match! {
+            target_os = "linux" => linux::sparc_info(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(target_arch = "arm")]
+    pub fn arm_info() -> Result<crate::cpu::ArmInfo, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::arm_info(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+    pub fn midr() -> Option<u32> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::midr(),
+            _ => None,
+        }
+    }
+
+    pub fn logical_cpus() -> Result<Vec<LogicalCpu>, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::logical_cpus(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn topology() -> Result<Topology, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::topology(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn effective_parallelism() -> Result<u32, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::effective_parallelism(),
+            target_family = "unix" => match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+                ..0 => Err(crate::helper::errno()),
+                n => Ok(n as u32),
+            },
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn memory() -> Result<Memory, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::memory(),
+            any(target_family = "unix", target_family = "windows") => os_memory(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn dmi() -> Result<crate::dmi::Dmi, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::dmi(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn board() -> Result<crate::board::Board, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::board(),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn virtualization() -> Result<crate::virt::Virtualization, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => linux::virtualization(),
+            any(target_arch = "x86_64", target_arch = "x86") => Ok(x86::hypervisor_signature()
+                .map_or(crate::virt::Virtualization::None, virt::from_cpuid_signature)),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn container() -> Result<Option<crate::virt::Container>, RawOsError> {
+        cfg_match::cfg_match! {
+            any(target_os = "linux", target_os = "android") => Ok(container::detect("/")),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    pub fn wsl() -> Result<Option<crate::virt::Wsl>, RawOsError> {
+        cfg_match::cfg_match! {
+            target_os = "linux" => Ok(wsl::detect("/")),
+            _ => Err(UNSUPPORTED),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    mod linux;
+
     #[cfg(target_arch = "x86_64")]
     mod x86_64;
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-    mod x86;
+    pub mod x86;
+
+    pub mod arm;
+
+    pub mod riscv;
+
+    pub mod powerpc;
+
+    pub mod s390x;
+
+    pub mod loongarch;
+
+    pub mod mips;
+
+    pub mod sparc;
+
+    pub mod smbios;
+
+    pub mod devicetree;
+
+    pub mod virt;
+
+    #[cfg(unix)]
+    pub mod container;
+
+    pub mod wsl;
 }
 
 /// A Generic Error type.
@@ -120,6 +374,21 @@ pub type Error = error_repr::Error<error::ErrorKind>;
 /// Types for error codes
 pub mod error;
 
+/// Detailed information about the host processor
+pub mod cpu;
+
+/// Parsers for information that Linux reports about the host
+pub mod linux;
+
+/// The identity of the system reported by the firmware
+pub mod dmi;
+
+/// The identity of the board described by the device tree
+pub mod board;
+
+/// Detection of virtual machines, containers, and WSL
+pub mod virt;
+
 /// Determines the complete name of the system.
 ///
 ///
@@ -144,6 +413,106 @@ pub fn uname() -> Result<Uname, Error> {
     Ok(uname)
 }
 
+/// Determines the memory installed in the host, and the memory available to the current process.
+///
+/// # Implementation
+///
+/// On unix-like systems, this uses `sysconf` to determine the total memory and page size.
+/// On Linux, this additionally reads `/proc/meminfo`, the page size from the auxiliary vector, the huge page sizes from `/sys/kernel/mm/hugepages`,
+/// and the `memory.max` (cgroup v2) or `memory.limit_in_bytes` (cgroup v1) of the cgroup of the current process.
+///
+/// On Windows this uses `GlobalMemoryStatusEx`, `GetSystemInfo`, and `GetLargePageMinimum`.
+///
+/// # Errors
+/// Returns an [`Error`] if the memory cannot be determined on the host.
+pub fn memory() -> Result<Memory, Error> {
+    imp::memory().map_err(Error::from_raw_os_error)
+}
+
+/// Determines the identity of the system (such as its vendor, product name, and BIOS version) from the firmware's SMBIOS tables.
+///
+/// # Implementation
+///
+/// On Linux, this reads the files in `/sys/class/dmi/id`. Any fields that are not available there (such as the serial numbers, if they are not readable)
+/// are read from the raw table in `/sys/firmware/dmi/tables/DMI`, if the current process can read it.
+///
+/// # Errors
+/// Returns an [`Error`] if the system has no SMBIOS tables (such as most Arm and RISC-V boards), or they are not available on the host OS.
+pub fn dmi() -> Result<dmi::Dmi, Error> {
+    imp::dmi().map_err(Error::from_raw_os_error)
+}
+
+/// Identifies the board from its device tree, such as a Raspberry Pi, NVIDIA Jetson, or an Apple Silicon Mac running Asahi Linux.
+///
+/// # Implementation
+///
+/// On Linux, this reads the `model` and `compatible` properties from `/proc/device-tree` (or `/sys/firmware/devicetree/base`).
+/// The revision of a Raspberry Pi is read from the `Revision` line of `/proc/cpuinfo`, or the `system/linux,revision` property.
+///
+/// # Errors
+/// Returns an [`Error`] if the system was not booted with a device tree (such as x86 and most servers, which use [`dmi()`] instead).
+pub fn board() -> Result<board::Board, Error> {
+    imp::board().map_err(Error::from_raw_os_error)
+}
+
+/// Determines whether the system is running in a virtual machine, and if so, which hypervisor it is running under.
+///
+/// # Implementation
+///
+/// On x86, this reads the hypervisor bit of cpuid leaf 1, and the hypervisor vendor signature in leaf `0x4000_0000`.
+///
+/// On Linux, this additionally uses:
+/// * The vendor and product strings of the SMBIOS tables (see [`dmi()`]), which identify hypervisors such as Amazon EC2 that present themselves to cpuid as KVM
+///   (on x86, Amazon EC2 and Google Compute Engine are only reported if cpuid also reports a hypervisor, as their bare-metal instances have the same strings),
+/// * The absence of both SMBIOS tables and a PCI bus in a KVM guest, which identifies Firecracker,
+/// * `/sys/hypervisor` and `/proc/xen`, to distinguish Xen PV and HVM guests from dom0,
+/// * The `/hypervisor` node of the device tree, and the nodes that identify PowerVM and QEMU,
+/// * The `Control Program` lines of `/proc/sysinfo` on IBM Z.
+///
+/// Without cpuid (such as on AArch64), a KVM guest is usually reported as [`Virtualization::Qemu`][virt::Virtualization::Qemu] (or whichever virtual machine monitor names itself in DMI).
+///
+/// # Errors
+/// Returns an [`Error`] if virtualization cannot be detected on the host OS.
+pub fn virtualization() -> Result<virt::Virtualization, Error> {
+    imp::virtualization().map_err(Error::from_raw_os_error)
+}
+
+/// Determines whether the current process is running in a container (such as Docker, LXC, or Kubernetes) or an application sandbox (such as Flatpak), and which one.
+///
+/// This is independent of [`virtualization()`]: a container may itself run in a virtual machine.
+///
+/// # Implementation
+///
+/// On Linux, this checks, in order:
+/// * `/.flatpak-info`, and the `SNAP_NAME` and `KUBERNETES_SERVICE_HOST` environment variables,
+/// * `/run/.containerenv` (Podman) and `/.dockerenv` (Docker),
+/// * The `container=` environment variable of init, from `/run/systemd/container` or `/proc/1/environ`,
+/// * The cgroup paths in `/proc/self/cgroup`, and the mount sources in `/proc/self/mountinfo`,
+/// * A `microsoft` kernel release in `/proc/sys/kernel/osrelease` (WSL).
+///
+/// See [`Container::detect_in`][virt::Container::detect_in] to check the files under another directory.
+///
+/// # Errors
+/// Returns an [`Error`] if containers cannot be detected on the host OS.
+pub fn container() -> Result<Option<virt::Container>, Error> {
+    imp::container().map_err(Error::from_raw_os_error)
+}
+
+/// Determines whether the system is a Windows Subsystem for Linux environment, and if so, its version, the Windows build (on WSL1),
+/// whether Windows programs can be run, and the name of the distribution.
+///
+/// # Implementation
+///
+/// On Linux, this recognizes the kernel release in `/proc/sys/kernel/osrelease` (see [`Wsl::from_kernel_release`][virt::Wsl::from_kernel_release]),
+/// reads `/proc/sys/fs/binfmt_misc/WSLInterop` (or `WSLInterop-late`) to determine whether interop is enabled,
+/// and reads `WSL_DISTRO_NAME` from the environment of the current process.
+///
+/// # Errors
+/// Returns an [`Error`] if the host OS is not Linux (including when running on Windows itself).
+pub fn wsl() -> Result<Option<virt::Wsl>, Error> {
+    imp::wsl().map_err(Error::from_raw_os_error)
+}
+
 // /// Additional functions used to support guessing the hardware target
 // #[cfg(feature = "guess")]
 // pub mod target;
@@ -0,0 +1,155 @@
+use alloc::{string::String, vec::Vec};
+
+/// A group of `key : value` lines from `/proc/cpuinfo`.
+///
+/// Keys are kept exactly as the kernel writes them, so the same information may be under different keys on different architectures.
+/// The accessors (such as [`CpuinfoRecord::model_name`]) check each of the keys used for that information.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CpuinfoRecord {
+    /// Each key and value, in the order they appear in the file
+    pub fields: Vec<(String, String)>,
+}
+
+impl CpuinfoRecord {
+    /// Returns the value of the first field named `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| &**v)
+    }
+
+    fn get_any(&self, keys: &[&str]) -> Option<&str> {
+        keys.iter().find_map(|key| self.get(key))
+    }
+
+    /// Returns the name of the processor.
+    ///
+    /// This is `model name` on x86 and Arm, `cpu model` on MIPS, `Model Name` on LoongArch, `cpu` on PowerPC and SPARC, and `uarch` on RISC-V.
+    /// On AArch64 and IBM Z, Linux does not report a name.
+    pub fn model_name(&self) -> Option<&str> {
+        self.get_any(&[
+            "model name",
+            "Model Name",
+            "cpu model",
+            "Processor",
+            "cpu",
+            "uarch",
+        ])
+    }
+
+    /// Returns the feature flags of the processor.
+    ///
+    /// This is `flags` on x86, `Features` on Arm and LoongArch, and `features` on IBM Z. Other architectures report no flags.
+    pub fn flags(&self) -> impl Iterator<Item = &str> {
+        self.get_any(&["flags", "Features", "features"])
+            .unwrap_or("")
+            .split_whitespace()
+    }
+
+    /// Returns whether `flag` is one of [`CpuinfoRecord::flags`].
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags().any(|f| f == flag)
+    }
+
+    /// Returns the microcode revision loaded on the processor (x86 only).
+    pub fn microcode(&self) -> Option<u64> {
+        let microcode = self.get("microcode")?;
+        match microcode.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => microcode.parse().ok(),
+        }
+    }
+
+    /// Returns the BogoMIPS calibrated by the kernel.
+    ///
+    /// On IBM Z, this is `bogomips per cpu`. PowerPC and RISC-V do not report BogoMIPS.
+    pub fn bogomips(&self) -> Option<f64> {
+        self.get_any(&["bogomips", "BogoMIPS", "bogomips per cpu"])?
+            .parse()
+            .ok()
+    }
+
+    /// Returns the current clock speed of the processor, in MHz.
+    ///
+    /// This is `cpu MHz` on x86 and MIPS, `CPU MHz` on LoongArch, `cpu MHz dynamic` on IBM Z, and `clock` on PowerPC.
+    pub fn cpu_mhz(&self) -> Option<f64> {
+        self.get_any(&["cpu MHz", "CPU MHz", "cpu MHz dynamic", "clock"])?
+            .trim_end_matches("MHz")
+            .parse()
+            .ok()
+    }
+}
+
+/// The contents of `/proc/cpuinfo`, split into a record for each processor.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Cpuinfo {
+    /// The fields of each processor.
+    ///
+    /// Each record also contains the [`Cpuinfo::shared`] fields it does not itself have, so that (for example) the `features` of IBM Z,
+    /// which are only reported once for the whole system, are available from each processor.
+    /// On SPARC, Linux only reports fields for the whole system, and this is empty.
+    pub processors: Vec<CpuinfoRecord>,
+    /// The fields that are not part of any one processor, such as `system type` on MIPS, `Hardware` on 32-bit Arm, `platform` on PowerPC,
+    /// or the header before the first processor on IBM Z.
+    pub shared: CpuinfoRecord,
+}
+
+impl Cpuinfo {
+    /// Parses the contents of a Linux `/proc/cpuinfo` file.
+    ///
+    /// A processor starts at a `processor : N` line (or `cpu number : N` on IBM Z), and ends at the next blank line.
+    /// Any other field belongs to [`Cpuinfo::shared`].
+    pub fn parse(cpuinfo: &str) -> Self {
+        let mut processors = Vec::<CpuinfoRecord>::new();
+        let mut shared = CpuinfoRecord::default();
+        let mut in_processor = false;
+
+        for line in cpuinfo.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                if line.trim().is_empty() {
+                    in_processor = false;
+                }
+                continue;
+            };
+
+            let (key, value) = (key.trim(), value.trim());
+
+            if matches!(key, "processor" | "cpu number") && value.parse::<u32>().is_ok() {
+                processors.push(CpuinfoRecord::default());
+                in_processor = true;
+            }
+
+            let record = match processors.last_mut() {
+                Some(record) if in_processor => record,
+                _ => &mut shared,
+            };
+
+            record.fields.push((key.into(), value.into()));
+        }
+
+        for record in &mut processors {
+            for (key, value) in &shared.fields {
+                if record.get(key).is_none() {
+                    record.fields.push((key.clone(), value.clone()));
+                }
+            }
+        }
+
+        Self { processors, shared }
+    }
+}
+
+/// Reads and parses `/proc/cpuinfo`.
+///
+/// # Errors
+/// Returns an [`Error`][crate::Error] if `/proc/cpuinfo` cannot be read.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn cpuinfo() -> Result<Cpuinfo, crate::Error> {
+    let cpuinfo =
+        crate::helper::read_file(c"/proc/cpuinfo").map_err(crate::Error::from_raw_os_error)?;
+
+    Ok(Cpuinfo::parse(&String::from_utf8_lossy(&cpuinfo)))
+}
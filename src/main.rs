@@ -21,6 +21,43 @@ bitflags::bitflags! {
         const HARDWARE_PLATFORM = 0x40;
         const OPERATING_SYSTEM = 0x80;
         const GUESS = 0x100;
+        const TARGET_FEATURES = 0x200;
+        const MEMORY = 0x400;
+        const DMI = 0x800;
+        const BOARD = 0x1000;
+        const VIRTUALIZATION = 0x2000;
+        const CONTAINER = 0x4000;
+        const WSL = 0x8000;
+        const PROCESSOR_FLAG = 0x10000;
+    }
+}
+
+impl UnameOption {
+    /// The options printed by `--all`
+    const ALL_UNAME: Self = Self::from_bits_truncate(0xFF);
+
+    /// The label of the option named `name` in `--verbose` output
+    fn label(name: &str) -> &str {
+        match name {
+            "KERNEL_NAME" => "Kernel Name",
+            "NODENAME" => "Nodename",
+            "KERNEL_RELEASE" => "Kernel Release",
+            "KERNEL_VERSION" => "Kernel Version",
+            "MACHINE" => "Machine",
+            "PROCESSOR" => "Processor",
+            "HARDWARE_PLATFORM" => "Hardware Platform",
+            "OPERATING_SYSTEM" => "Operating System",
+            "GUESS" => "Target",
+            "TARGET_FEATURES" => "Target Features",
+            "MEMORY" => "Memory",
+            "DMI" => "DMI",
+            "BOARD" => "Board",
+            "VIRTUALIZATION" => "Virtualization",
+            "CONTAINER" => "Container",
+            "WSL" => "WSL",
+            "PROCESSOR_FLAG" => "Processor Flag",
+            x => x,
+        }
     }
 }
 
@@ -32,12 +69,14 @@ fn main() {
     let prg_name = args.next().unwrap();
 
     let mut options = UnameOption::empty();
+    let mut verbose = false;
+    let mut dialect = sysname::cpu::Dialect::Gcc;
 
     while let Some(arg) = args.next() {
         let arg = &*arg;
 
         match arg {
-            "--all" => options |= UnameOption::all() & !UnameOption::GUESS,
+            "--all" => options |= UnameOption::ALL_UNAME,
             "--kernel-name" => options |= UnameOption::KERNEL_NAME,
             "--nodename" => options |= UnameOption::NODENAME,
             "--kernel-release" => options |= UnameOption::KERNEL_RELEASE,
@@ -46,6 +85,14 @@ fn main() {
             "--processor" => options |= UnameOption::PROCESSOR,
             "--hardware-platform" => options |= UnameOption::HARDWARE_PLATFORM,
             "--operating-system" => options |= UnameOption::OPERATING_SYSTEM,
+            "--target-features" => options |= UnameOption::TARGET_FEATURES,
+            "--memory" => options |= UnameOption::MEMORY,
+            "--dmi" => options |= UnameOption::DMI,
+            "--board" => options |= UnameOption::BOARD,
+            "--virtualization" => options |= UnameOption::VIRTUALIZATION,
+            "--container" => options |= UnameOption::CONTAINER,
+            "--wsl" => options |= UnameOption::WSL,
+            "--verbose" => verbose = true,
             // #[cfg(feature = "guess")]
             // "--guess" => options |= UnameOption::GUESS,
             "--help" => {
@@ -61,6 +108,34 @@ fn main() {
                 println!("\t--processor, -p: Prints the host processor (non-portable)");
                 println!("\t--hardware-platform, -i: Prints the hardware platform (non-portable)");
                 println!("\t--operating-system, -o: Prints the operating system name");
+                println!("Additional Options (not included in --all):");
+                println!(
+                    "\t--target-features: Prints the host processor features as rustc flags (non-portable)"
+                );
+                println!(
+                    "\t--memory: Prints the total, available, and cgroup-limited memory, and the supported page sizes"
+                );
+                println!(
+                    "\t--dmi: Prints the system vendor, product name, and BIOS version reported by the firmware"
+                );
+                println!(
+                    "\t--board: Prints the board (such as a Raspberry Pi or Jetson) described by the device tree"
+                );
+                println!(
+                    "\t--virtualization: Prints the hypervisor the system is running under (like systemd-detect-virt), or none"
+                );
+                println!(
+                    "\t--container: Prints the container runtime or sandbox the program is running in, and its ID if known, or none"
+                );
+                println!(
+                    "\t--wsl: Prints the WSL version, Windows build, interop status, and distribution name, or none"
+                );
+                println!(
+                    "\t--march=<gcc|clang|rustc|msvc|go>: Prints the flag selecting the host processor for the compiler, such as -march=alderlake, -C target-cpu=alderlake, /arch:AVX2, or GOAMD64=v3 (non-portable)"
+                );
+                println!(
+                    "\t--verbose: Prints the --all information and the board, and any other options, one per line with labels"
+                );
                 // #[cfg(feature = "guess")]
                 // println!("\t--guess: Prints the target tuple (non-portable)");
                 println!("\t--help: Prints this message and exits");
@@ -86,6 +161,20 @@ fn main() {
 
                 std::process::exit(0)
             }
+            x if x.starts_with("--march=") => {
+                dialect = match &x["--march=".len()..] {
+                    "gcc" => sysname::cpu::Dialect::Gcc,
+                    "clang" => sysname::cpu::Dialect::Clang,
+                    "rustc" => sysname::cpu::Dialect::Rustc,
+                    "msvc" => sysname::cpu::Dialect::Msvc,
+                    "go" => sysname::cpu::Dialect::Go,
+                    name => {
+                        eprintln!("{prg_name}: Unknown compiler for --march: {name}");
+                        std::process::exit(1)
+                    }
+                };
+                options |= UnameOption::PROCESSOR_FLAG;
+            }
             x if x.starts_with("--") => {
                 eprintln!("{prg_name}: Unknown Option: {x}");
                 std::process::exit(1)
@@ -93,7 +182,7 @@ fn main() {
             x if x.starts_with("-") => {
                 for c in x.chars().skip(1) {
                     match c {
-                        'a' => options |= UnameOption::all() & !UnameOption::GUESS,
+                        'a' => options |= UnameOption::ALL_UNAME,
                         's' => options |= UnameOption::KERNEL_NAME,
                         'n' => options |= UnameOption::NODENAME,
                         'r' => options |= UnameOption::KERNEL_RELEASE,
@@ -120,13 +209,20 @@ fn main() {
     //     options |= UnameOption::GUESS
     // }
 
-    if options.is_empty() {
+    if verbose {
+        options |= UnameOption::ALL_UNAME | UnameOption::BOARD;
+    } else if options.is_empty() {
         options = UnameOption::KERNEL_NAME;
     }
 
     let mut sep = "";
 
     for (name, _) in options.iter_names() {
+        if verbose {
+            print!("{sep}{}: ", UnameOption::label(name));
+            sep = "";
+        }
+
         match name {
             "KERNEL_NAME" => print!("{sep}{}", uname.kernel_name),
             "NODENAME" => print!("{sep}{}", uname.nodename),
@@ -136,11 +232,69 @@ fn main() {
             "PROCESSOR" => print!("{sep}{}", uname.processor),
             "HARDWARE_PLATFORM" => print!("{sep}{}", uname.hardware_platform),
             "OPERATING_SYSTEM" => print!("{sep}{}", uname.sysname),
+            "TARGET_FEATURES" => match sysname::cpu::target_features() {
+                Some(features) => print!("{sep}-C target-feature={features}"),
+                None => print!("{sep}unknown"),
+            },
+            "MEMORY" => match sysname::memory() {
+                Ok(memory) => {
+                    print!("{sep}total={}", memory.total);
+                    if let Some(available) = memory.available {
+                        print!(" available={available}");
+                    }
+                    if let Some(limit) = memory.cgroup_limit {
+                        print!(" cgroup_limit={limit}");
+                    }
+                    print!(" page_size={}", memory.page_size);
+                    for size in &memory.huge_page_sizes {
+                        print!(" huge_page_size={size}");
+                    }
+                }
+                Err(_) => print!("{sep}unknown"),
+            },
+            "DMI" => match sysname::dmi() {
+                Ok(dmi) => print!("{sep}{dmi}"),
+                Err(_) => print!("{sep}unknown"),
+            },
+            "BOARD" => match sysname::board() {
+                Ok(board) => print!("{sep}{board}"),
+                Err(_) => print!("{sep}unknown"),
+            },
+            "VIRTUALIZATION" => match sysname::virtualization() {
+                Ok(virt) => print!("{sep}{virt}"),
+                Err(_) => print!("{sep}unknown"),
+            },
+            "CONTAINER" => match sysname::container() {
+                Ok(Some(container)) => print!("{sep}{container}"),
+                Ok(None) => print!("{sep}none"),
+                Err(_) => print!("{sep}unknown"),
+            },
+            "WSL" => match sysname::wsl() {
+                Ok(Some(wsl)) => {
+                    print!("{sep}{}", wsl.version);
+                    if let Some(build) = wsl.windows_build {
+                        print!(" windows_build={build}");
+                    }
+                    print!(" interop={}", wsl.interop);
+                    if let Some(name) = &wsl.distro_name {
+                        print!(" distro={name}");
+                    }
+                }
+                Ok(None) => print!("{sep}none"),
+                Err(_) => print!("{sep}unknown"),
+            },
+            "PROCESSOR_FLAG" => match sysname::cpu::processor_name()
+                .ok()
+                .and_then(|name| name.flag(dialect))
+            {
+                Some(flag) => print!("{sep}{flag}"),
+                None => print!("{sep}unknown"),
+            },
             // #[cfg(feature = "guess")]
             // "GUESS" => todo!("Implement config.guess"),
             x => todo!("Flag {x}"),
         }
-        sep = " ";
+        sep = if verbose { "\n" } else { " " };
     }
 
     println!();
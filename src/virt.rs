@@ -0,0 +1,244 @@
+use alloc::string::String;
+
+/// The hypervisor (if any) that the system is running under.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Virtualization {
+    /// Not running in a virtual machine (including the host, or Xen dom0)
+    None,
+    /// Linux KVM, with any virtual machine monitor that does not identify itself otherwise (such as QEMU or Cloud Hypervisor)
+    Kvm,
+    /// QEMU without hardware acceleration (TCG)
+    Qemu,
+    /// The Bochs emulator
+    Bochs,
+    /// A paravirtualized Xen guest
+    XenPv,
+    /// A hardware-virtualized Xen guest (HVM or PVH)
+    XenHvm,
+    /// Microsoft Hyper-V
+    Microsoft,
+    /// VMware
+    VMware,
+    /// Oracle VirtualBox
+    VirtualBox,
+    /// Parallels
+    Parallels,
+    /// Apple's Virtualization framework
+    Apple,
+    /// Firecracker
+    Firecracker,
+    /// Amazon EC2 (the Nitro hypervisor)
+    Amazon,
+    /// Google Compute Engine
+    Google,
+    /// FreeBSD bhyve
+    Bhyve,
+    /// ACRN
+    Acrn,
+    /// QNX hypervisor
+    Qnx,
+    /// An IBM PowerVM logical partition
+    PowerVm,
+    /// IBM z/VM
+    Zvm,
+    /// A hypervisor that is not recognized
+    Other,
+}
+
+impl Virtualization {
+    /// Returns the name of the hypervisor, as printed by `systemd-detect-virt` (such as `kvm`, `microsoft`, or `oracle`).
+    ///
+    /// `systemd-detect-virt` does not distinguish Xen PV from HVM (both are `xen`) and does not name Firecracker, which are `xen-pv` and `firecracker` here.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Kvm => "kvm",
+            Self::Qemu => "qemu",
+            Self::Bochs => "bochs",
+            Self::XenPv => "xen-pv",
+            Self::XenHvm => "xen",
+            Self::Microsoft => "microsoft",
+            Self::VMware => "vmware",
+            Self::VirtualBox => "oracle",
+            Self::Parallels => "parallels",
+            Self::Apple => "apple",
+            Self::Firecracker => "firecracker",
+            Self::Amazon => "amazon",
+            Self::Google => "google",
+            Self::Bhyve => "bhyve",
+            Self::Acrn => "acrn",
+            Self::Qnx => "qnx",
+            Self::PowerVm => "powervm",
+            Self::Zvm => "zvm",
+            Self::Other => "vm-other",
+        }
+    }
+
+    /// Returns whether the system is running in a virtual machine.
+    pub fn is_vm(self) -> bool {
+        self != Self::None
+    }
+
+    /// Identifies the hypervisor from the vendor signature in `ebx`, `ecx`, and `edx` of x86 cpuid leaf `0x4000_0000`, such as `KVMKVMKVM\0\0\0`.
+    ///
+    /// Returns [`Virtualization::Other`] if the signature is not recognized.
+    pub fn from_cpuid_signature(signature: [u8; 12]) -> Self {
+        crate::imp::virt::from_cpuid_signature(signature)
+    }
+
+    /// Identifies the hypervisor from the vendor and product strings in the SMBIOS tables, if they name one.
+    pub fn from_dmi(dmi: &crate::dmi::Dmi) -> Option<Self> {
+        crate::imp::virt::from_dmi(dmi)
+    }
+}
+
+impl core::fmt::Display for Virtualization {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The container runtime or application sandbox that the current process is running in.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContainerRuntime {
+    /// Docker
+    Docker,
+    /// Podman
+    Podman,
+    /// LXC, including containers managed by LXD and Incus
+    Lxc,
+    /// systemd-nspawn
+    SystemdNspawn,
+    /// A Kubernetes pod, with any container runtime
+    Kubernetes,
+    /// A Windows Subsystem for Linux distribution
+    Wsl,
+    /// A Flatpak sandbox
+    Flatpak,
+    /// A Snap confinement
+    Snap,
+    /// A container runtime that is not recognized
+    Other,
+}
+
+impl ContainerRuntime {
+    /// Returns the name of the container runtime, as printed by `systemd-detect-virt --container` (such as `docker` or `systemd-nspawn`).
+    ///
+    /// `systemd-detect-virt` does not name Kubernetes, Flatpak, or Snap, which are `kubernetes`, `flatpak`, and `snap` here.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+            Self::Lxc => "lxc",
+            Self::SystemdNspawn => "systemd-nspawn",
+            Self::Kubernetes => "kubernetes",
+            Self::Wsl => "wsl",
+            Self::Flatpak => "flatpak",
+            Self::Snap => "snap",
+            Self::Other => "container-other",
+        }
+    }
+}
+
+impl core::fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Describes the container that the current process is running in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Container {
+    /// The container runtime
+    pub runtime: ContainerRuntime,
+    /// The identifier of the container, if it could be found.
+    ///
+    /// This is the 64 digit container ID for Docker and Podman (and Kubernetes, if the pod's cgroups are visible, and otherwise the pod name),
+    /// the container name for LXC, the application ID for Flatpak, the snap name for Snap, and the distribution name for WSL.
+    pub id: Option<String>,
+}
+
+impl Container {
+    /// Detects the container that the current process is running in, using the files under `root` instead of `/`.
+    ///
+    /// This allows detection to be tested against a copy of the relevant files, or to inspect another process' view of the system
+    /// (such as by passing `/proc/<pid>/root`, though `/proc/self` will still refer to the current process within that).
+    ///
+    /// Returns `None` if there is no evidence of a container.
+    #[cfg(unix)]
+    pub fn detect_in(root: &str) -> Option<Self> {
+        crate::imp::container::detect(root)
+    }
+}
+
+impl core::fmt::Display for Container {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "{} {id}", self.runtime),
+            None => write!(f, "{}", self.runtime),
+        }
+    }
+}
+
+/// The version of the Windows Subsystem for Linux.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum WslVersion {
+    /// WSL1, which translates Linux system calls in the Windows kernel
+    Wsl1,
+    /// WSL2, which runs a Linux kernel in a lightweight virtual machine
+    Wsl2,
+}
+
+impl WslVersion {
+    /// Returns the name of the version, `wsl1` or `wsl2`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wsl1 => "wsl1",
+            Self::Wsl2 => "wsl2",
+        }
+    }
+}
+
+impl core::fmt::Display for WslVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Describes the Windows Subsystem for Linux environment that the current process is running in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Wsl {
+    /// The version of WSL
+    pub version: WslVersion,
+    /// The build number of the Windows host, such as `19041`.
+    ///
+    /// This is only known on WSL1, which reports it in the kernel release. WSL2 runs its own kernel, whose release does not depend on the host.
+    pub windows_build: Option<u32>,
+    /// Whether Windows programs can be run from Linux (the `WSLInterop` binfmt_misc entry is registered and enabled)
+    pub interop: bool,
+    /// The name of the WSL distribution, from the `WSL_DISTRO_NAME` environment variable, such as `Ubuntu-24.04`
+    pub distro_name: Option<String>,
+}
+
+impl Wsl {
+    /// Recognizes the kernel release (see [`Uname::kernel_release`][crate::Uname::kernel_release]) of WSL1 (such as `4.4.0-19041-Microsoft`)
+    /// or WSL2 (such as `5.15.153.1-microsoft-standard-WSL2`), and returns `None` for any other kernel.
+    ///
+    /// Only the kernel release is inspected, so [`Wsl::interop`] is `false` and [`Wsl::distro_name`] is `None`.
+    pub fn from_kernel_release(release: &str) -> Option<Self> {
+        crate::imp::wsl::parse_kernel_release(release)
+    }
+
+    /// Detects WSL, using the files under `root` instead of `/`, in the same way as [`Container::detect_in`].
+    ///
+    /// Returns `None` if the kernel is not a WSL kernel.
+    #[cfg(unix)]
+    pub fn detect_in(root: &str) -> Option<Self> {
+        crate::imp::wsl::detect(root)
+    }
+}
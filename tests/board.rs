@@ -0,0 +1,108 @@
+use sysname::board::{Board, RaspberryPiRevision};
+
+#[test]
+fn raspberry_pi_old_style() {
+    let rev = RaspberryPiRevision::from_code(0x000e);
+    assert_eq!(rev.model, Some("Model B"));
+    assert_eq!(rev.revision, (2, 0));
+    assert_eq!(rev.memory_mb, Some(512));
+    assert_eq!(rev.processor, Some("BCM2835"));
+    assert_eq!(rev.manufacturer, Some("Sony UK"));
+
+    // Bit 24 marks a board that has been overvolted
+    let overvolted = RaspberryPiRevision::from_code(0x0100_000e);
+    assert_eq!(overvolted.code, 0x0100_000e);
+    assert_eq!(overvolted.model, Some("Model B"));
+
+    let unknown = RaspberryPiRevision::from_code(0x0001);
+    assert_eq!(unknown.model, None);
+    assert_eq!(unknown.processor, None);
+}
+
+#[test]
+fn raspberry_pi_new_style() {
+    let rev = RaspberryPiRevision::from_code(0xc03114);
+    assert_eq!(rev.model, Some("4 Model B"));
+    assert_eq!(rev.revision, (1, 4));
+    assert_eq!(rev.memory_mb, Some(4096));
+    assert_eq!(rev.processor, Some("BCM2711"));
+    assert_eq!(rev.manufacturer, Some("Sony UK"));
+
+    let rev = RaspberryPiRevision::from_code(0x902120);
+    assert_eq!(rev.model, Some("Zero 2 W"));
+    assert_eq!(rev.revision, (1, 0));
+    assert_eq!(rev.memory_mb, Some(512));
+    assert_eq!(rev.processor, Some("BCM2837"));
+    assert_eq!(rev.manufacturer, Some("Sony UK"));
+}
+
+#[test]
+fn raspberry_pi_board() {
+    let board = Board::from_device_tree(
+        Some("Raspberry Pi 4 Model B Rev 1.4\0"),
+        b"raspberrypi,4-model-b\0brcm,bcm2711\0",
+        Some(0xc03114),
+    );
+    assert_eq!(
+        board.model.as_deref(),
+        Some("Raspberry Pi 4 Model B Rev 1.4")
+    );
+    assert_eq!(board.compatible, ["raspberrypi,4-model-b", "brcm,bcm2711"]);
+    assert_eq!(board.vendor, Some("Raspberry Pi"));
+    assert_eq!(
+        board.name.as_deref(),
+        Some("Raspberry Pi 4 Model B Rev 1.4")
+    );
+    assert_eq!(board.raspberry_pi.map(|rev| rev.code), Some(0xc03114));
+
+    // The revision code is only meaningful on a Raspberry Pi
+    let board = Board::from_device_tree(None, b"radxa,rock-5b\0rockchip,rk3588\0", Some(0xc03114));
+    assert_eq!(board.raspberry_pi, None);
+}
+
+#[test]
+fn known_boards() {
+    let boards: [(&[u8], &str, &str); 4] = [
+        (
+            b"nvidia,p3737-0000+p3701-0005\0nvidia,p3701-0005\0nvidia,tegra234\0",
+            "NVIDIA",
+            "Jetson AGX Orin 64GB",
+        ),
+        (b"radxa,rock-5b\0rockchip,rk3588\0", "Radxa", "ROCK 5B"),
+        (
+            b"starfive,visionfive-2-v1.3b\0starfive,jh7110\0",
+            "StarFive",
+            "VisionFive 2 v1.3B",
+        ),
+        (
+            b"apple,j413\0apple,t8112\0apple,arm-platform\0",
+            "Apple",
+            "MacBook Air (M2, 2022)",
+        ),
+    ];
+
+    for (compatible, vendor, name) in boards {
+        let board = Board::from_device_tree(None, compatible, None);
+        assert_eq!(board.vendor, Some(vendor), "{name}");
+        assert_eq!(board.name.as_deref(), Some(name));
+        assert_eq!(board.to_string(), name);
+    }
+}
+
+#[test]
+fn unknown_board() {
+    let board = Board::from_device_tree(
+        Some("Firefly ROC-RK3399-PC Board"),
+        b"firefly,roc-rk3399-pc\0rockchip,rk3399\0",
+        None,
+    );
+    assert_eq!(board.vendor, Some("Rockchip"));
+    assert_eq!(board.name, None);
+    assert_eq!(board.to_string(), "Firefly ROC-RK3399-PC Board");
+
+    let board = Board::from_device_tree(None, b"acme,widget\0", None);
+    assert_eq!(board.vendor, None);
+    assert_eq!(board.to_string(), "acme,widget");
+
+    assert_eq!(Board::from_device_tree(None, b"", None), Board::default());
+}
@@ -0,0 +1,237 @@
+#![cfg(unix)]
+
+use std::path::PathBuf;
+
+use sysname::virt::{Container, ContainerRuntime, Wsl, WslVersion};
+
+const DOCKER_ID: &str = "4f1d2e0b6c8a9d3e5f7a1b2c3d4e5f60718293a4b5c6d7e8f901122334455667";
+
+/// Creates a fake root directory containing `files`
+fn fake_root(name: &str, files: &[(&str, &[u8])]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("sysname-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+
+    for (path, contents) in files {
+        let path = root.join(path.trim_start_matches('/'));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+fn detect(name: &str, files: &[(&str, &[u8])]) -> Option<Container> {
+    let root = fake_root(name, files);
+    let container = Container::detect_in(root.to_str().unwrap());
+    std::fs::remove_dir_all(root).unwrap();
+    container
+}
+
+#[test]
+fn none() {
+    assert_eq!(detect("none", &[("/proc/self/cgroup", b"0::/\n")]), None);
+}
+
+#[test]
+fn docker() {
+    let mountinfo = format!(
+        "612 590 0:52 / / rw,relatime master:283 - overlay overlay rw\n\
+         629 612 259:2 /var/lib/docker/containers/{DOCKER_ID}/resolv.conf /etc/resolv.conf rw,relatime - ext4 /dev/nvme0n1p2 rw\n"
+    );
+
+    let container = detect(
+        "docker",
+        &[
+            ("/.dockerenv", b""),
+            ("/proc/self/cgroup", b"0::/\n"),
+            ("/proc/self/mountinfo", mountinfo.as_bytes()),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Docker);
+    assert_eq!(container.id.as_deref(), Some(DOCKER_ID));
+}
+
+#[test]
+fn docker_cgroup_v1() {
+    let cgroup = format!("12:memory:/docker/{DOCKER_ID}\n0::/\n");
+
+    let container = detect("docker-cgroup", &[("/proc/self/cgroup", cgroup.as_bytes())]).unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Docker);
+    assert_eq!(container.id.as_deref(), Some(DOCKER_ID));
+}
+
+#[test]
+fn docker_cgroup_v2() {
+    let cgroup = format!("0::/system.slice/docker-{DOCKER_ID}.scope\n");
+
+    let container = detect(
+        "docker-cgroup-v2",
+        &[("/proc/self/cgroup", cgroup.as_bytes())],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Docker);
+    assert_eq!(container.id.as_deref(), Some(DOCKER_ID));
+}
+
+#[test]
+fn docker_host() {
+    // The Docker daemon and its sockets are not containers
+    for cgroup in [
+        "0::/system.slice/docker.service\n",
+        "0::/system.slice/docker.socket\n",
+        "12:memory:/docker\n0::/\n",
+        "0::/user.slice/user-1000.slice/docker-compose.scope\n",
+    ] {
+        assert_eq!(
+            detect("docker-host", &[("/proc/self/cgroup", cgroup.as_bytes())]),
+            None,
+            "{cgroup}"
+        );
+    }
+}
+
+#[test]
+fn podman() {
+    let containerenv = format!(
+        "engine=\"podman-4.9.3\"\nname=\"builder\"\nid=\"{DOCKER_ID}\"\nimage=\"docker.io/library/rust:latest\"\n"
+    );
+
+    let container = detect("podman", &[("/run/.containerenv", containerenv.as_bytes())]).unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Podman);
+    assert_eq!(container.id.as_deref(), Some(DOCKER_ID));
+}
+
+#[test]
+fn kubernetes() {
+    let container = detect(
+        "kubernetes",
+        &[
+            ("/.dockerenv", b""),
+            (
+                "/proc/self/environ",
+                b"PATH=/usr/bin\0HOSTNAME=web-7d4b9c8f6-x2k9p\0KUBERNETES_SERVICE_HOST=10.96.0.1\0",
+            ),
+            ("/proc/self/cgroup", b"0::/\n"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Kubernetes);
+    assert_eq!(container.id.as_deref(), Some("web-7d4b9c8f6-x2k9p"));
+}
+
+#[test]
+fn lxc() {
+    let container = detect(
+        "lxc",
+        &[
+            ("/run/systemd/container", b"lxc\n"),
+            ("/proc/self/cgroup", b"0::/lxc.payload.c1/init.scope\n"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Lxc);
+    assert_eq!(container.id.as_deref(), Some("c1"));
+}
+
+#[test]
+fn systemd_nspawn() {
+    let container = detect(
+        "nspawn",
+        &[("/proc/1/environ", b"TERM=vt220\0container=systemd-nspawn\0")],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::SystemdNspawn);
+    assert_eq!(container.id, None);
+}
+
+#[test]
+fn flatpak() {
+    let container = detect(
+        "flatpak",
+        &[(
+            "/.flatpak-info",
+            b"[Application]\nname=org.mozilla.firefox\nruntime=runtime/org.freedesktop.Platform/x86_64/23.08\n\n[Instance]\ninstance-id=1234\n",
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Flatpak);
+    assert_eq!(container.id.as_deref(), Some("org.mozilla.firefox"));
+}
+
+#[test]
+fn snap() {
+    let container = detect(
+        "snap",
+        &[(
+            "/proc/self/environ",
+            b"SNAP=/snap/code/150\0SNAP_NAME=code\0",
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Snap);
+    assert_eq!(container.id.as_deref(), Some("code"));
+}
+
+#[test]
+fn wsl() {
+    let container = detect(
+        "wsl",
+        &[
+            (
+                "/proc/sys/kernel/osrelease",
+                b"5.15.153.1-microsoft-standard-WSL2\n",
+            ),
+            ("/proc/self/environ", b"WSL_DISTRO_NAME=Ubuntu-24.04\0"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(container.runtime, ContainerRuntime::Wsl);
+    assert_eq!(container.id.as_deref(), Some("Ubuntu-24.04"));
+}
+
+#[test]
+fn wsl1() {
+    let root = fake_root(
+        "wsl1",
+        &[
+            ("/proc/sys/kernel/osrelease", b"4.4.0-19041-Microsoft\n"),
+            (
+                "/proc/sys/fs/binfmt_misc/WSLInterop",
+                b"enabled\ninterpreter /init\nflags: PF\noffset 0\nmagic 4d5a\n",
+            ),
+            ("/proc/self/environ", b"WSL_DISTRO_NAME=Debian\0"),
+        ],
+    );
+    let wsl = Wsl::detect_in(root.to_str().unwrap()).unwrap();
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert_eq!(wsl.version, WslVersion::Wsl1);
+    assert_eq!(wsl.windows_build, Some(19041));
+    assert!(wsl.interop);
+    assert_eq!(wsl.distro_name.as_deref(), Some("Debian"));
+}
+
+#[test]
+fn wsl2_kernel_release() {
+    let wsl = Wsl::from_kernel_release("5.15.153.1-microsoft-standard-WSL2").unwrap();
+    assert_eq!(wsl.version, WslVersion::Wsl2);
+    assert_eq!(wsl.windows_build, None);
+
+    assert_eq!(
+        Wsl::from_kernel_release("4.19.128-microsoft-standard").map(|wsl| wsl.version),
+        Some(WslVersion::Wsl2)
+    );
+    assert_eq!(Wsl::from_kernel_release("6.8.0-45-generic"), None);
+}
@@ -0,0 +1,127 @@
+use sysname::linux::Cpuinfo;
+
+macro_rules! fixture {
+    ($name:literal) => {
+        Cpuinfo::parse(include_str!(concat!("fixtures/cpuinfo/", $name, ".txt")))
+    };
+}
+
+#[test]
+fn x86_64() {
+    let cpuinfo = fixture!("x86_64-xeon-vm");
+    assert_eq!(cpuinfo.processors.len(), 1);
+
+    let cpu = &cpuinfo.processors[0];
+    assert_eq!(cpu.model_name(), Some("Intel(R) Xeon(R) Processor"));
+    assert_eq!(cpu.get("vendor_id"), Some("GenuineIntel"));
+    assert_eq!(cpu.microcode(), Some(1));
+    assert_eq!(cpu.bogomips(), Some(4200.0));
+    assert_eq!(cpu.cpu_mhz(), Some(2100.0));
+    assert!(cpu.has_flag("avx512f"));
+    assert_eq!(cpu.get("power management"), Some(""));
+}
+
+#[test]
+fn aarch64() {
+    let cpuinfo = fixture!("aarch64-neoverse-n1");
+    assert_eq!(cpuinfo.processors.len(), 2);
+
+    let cpu = &cpuinfo.processors[1];
+    assert_eq!(cpu.get("processor"), Some("1"));
+    assert_eq!(cpu.get("CPU part"), Some("0xd0c"));
+    assert_eq!(cpu.model_name(), None);
+    assert_eq!(cpu.bogomips(), Some(50.0));
+    assert!(cpu.has_flag("asimddp"));
+    assert_eq!(cpu.microcode(), None);
+}
+
+#[test]
+fn armv7() {
+    let cpuinfo = fixture!("armv7-raspberry-pi-3");
+    assert_eq!(cpuinfo.processors.len(), 2);
+    assert_eq!(cpuinfo.shared.get("Hardware"), Some("BCM2835"));
+
+    let cpu = &cpuinfo.processors[0];
+    assert_eq!(cpu.model_name(), Some("ARMv7 Processor rev 4 (v7l)"));
+    assert_eq!(cpu.get("Hardware"), Some("BCM2835"));
+    assert!(cpu.has_flag("neon"));
+}
+
+#[test]
+fn s390x() {
+    let cpuinfo = fixture!("s390x-z16");
+    assert_eq!(cpuinfo.processors.len(), 2);
+    assert_eq!(cpuinfo.shared.get("vendor_id"), Some("IBM/S390"));
+
+    let cpu = &cpuinfo.processors[1];
+    assert_eq!(cpu.get("cpu number"), Some("1"));
+    assert_eq!(cpu.get("machine"), Some("3931"));
+    assert_eq!(cpu.model_name(), None);
+    assert_eq!(cpu.bogomips(), Some(3331.0));
+    assert_eq!(cpu.cpu_mhz(), Some(5200.0));
+    assert!(cpu.has_flag("nnpa"));
+}
+
+#[test]
+fn riscv64() {
+    let cpuinfo = fixture!("riscv64-visionfive-2");
+    assert_eq!(cpuinfo.processors.len(), 2);
+
+    let cpu = &cpuinfo.processors[0];
+    assert_eq!(cpu.model_name(), Some("sifive,u74-mc"));
+    assert_eq!(cpu.get("hart"), Some("2"));
+    assert_eq!(cpu.flags().count(), 0);
+    assert_eq!(cpu.bogomips(), None);
+}
+
+#[test]
+fn powerpc64le() {
+    let cpuinfo = fixture!("ppc64le-power9");
+    assert_eq!(cpuinfo.processors.len(), 2);
+    assert_eq!(cpuinfo.shared.get("platform"), Some("PowerNV"));
+
+    let cpu = &cpuinfo.processors[0];
+    assert_eq!(cpu.model_name(), Some("POWER9, altivec supported"));
+    assert_eq!(cpu.cpu_mhz(), Some(2166.0));
+    assert_eq!(cpu.get("MMU"), Some("Radix"));
+}
+
+#[test]
+fn mips() {
+    let cpuinfo = fixture!("mips-octeon3");
+    assert_eq!(cpuinfo.processors.len(), 2);
+    assert_eq!(cpuinfo.shared.get("system type"), Some("UBNT_E300"));
+
+    let cpu = &cpuinfo.processors[1];
+    assert_eq!(cpu.model_name(), Some("Cavium Octeon III V0.2  FPU V0.0"));
+    assert_eq!(cpu.get("core"), Some("1"));
+    assert_eq!(cpu.get("system type"), Some("UBNT_E300"));
+    assert_eq!(
+        cpu.get("hardware watchpoint"),
+        Some("yes, count: 2, address/irw mask: [0x0ffc, 0x0ffb]")
+    );
+    assert_eq!(cpu.bogomips(), Some(2000.0));
+}
+
+#[test]
+fn loongarch64() {
+    let cpuinfo = fixture!("loongarch64-3a5000");
+    assert_eq!(cpuinfo.processors.len(), 2);
+
+    let cpu = &cpuinfo.processors[0];
+    assert_eq!(cpu.model_name(), Some("Loongson-3A5000-HV"));
+    assert_eq!(cpu.cpu_mhz(), Some(2500.0));
+    assert_eq!(cpu.bogomips(), Some(5000.0));
+    assert!(cpu.has_flag("lasx"));
+}
+
+#[test]
+fn sparc64() {
+    let cpuinfo = fixture!("sparc64-t4");
+    assert!(cpuinfo.processors.is_empty());
+    assert_eq!(
+        cpuinfo.shared.model_name(),
+        Some("UltraSparc T4 (Niagara4)")
+    );
+    assert_eq!(cpuinfo.shared.get("type"), Some("sun4v"));
+}
@@ -0,0 +1,528 @@
+use sysname::cpu::{
+    Endianness, LoongArchFeatures, LoongArchInfo, MipsInfo, PowerAbi, PowerInfo, RiscvIsa,
+    RiscvProfile, S390xInfo, SparcInfo, midrs_from_cpuinfo, processor_from_midr,
+    riscv_processor_from_cpuinfo,
+};
+
+macro_rules! fixture {
+    ($name:literal) => {
+        include_str!(concat!("fixtures/cpuinfo/", $name, ".txt"))
+    };
+}
+
+#[test]
+fn midr_arm() {
+    assert_eq!(processor_from_midr(0x413f_d0c1), Some("neoverse-n1"));
+    assert_eq!(processor_from_midr(0x410f_d083), Some("cortex-a72"));
+    assert_eq!(processor_from_midr(0x410f_d034), Some("cortex-a53"));
+    assert_eq!(processor_from_midr(0x410f_d4f0), Some("neoverse-v2"));
+    assert_eq!(processor_from_midr(0x410f_c075), Some("cortex-a7"));
+    assert_eq!(processor_from_midr(0x410f_fff0), None);
+}
+
+#[test]
+fn midr_other_implementers() {
+    assert_eq!(processor_from_midr(0x431f_0af1), Some("thunderx2t99"));
+    assert_eq!(processor_from_midr(0x461f_0010), Some("a64fx"));
+    assert_eq!(processor_from_midr(0x481f_d010), Some("tsv110"));
+    assert_eq!(processor_from_midr(0x4e0f_0040), Some("carmel"));
+    assert_eq!(processor_from_midr(0x51df_804e), Some("cortex-a76"));
+    assert_eq!(processor_from_midr(0x611f_0221), Some("apple-m1"));
+    assert_eq!(processor_from_midr(0x611f_0481), Some("apple-m3"));
+    assert_eq!(processor_from_midr(0xc00f_ac30), Some("ampere1"));
+    assert_eq!(processor_from_midr(0x000f_0000), None);
+}
+
+#[test]
+fn midr_samsung() {
+    assert_eq!(processor_from_midr(0x531f_0030), Some("exynos-m4"));
+    // Every other Exynos M core falls back to the oldest one LLVM knows
+    assert_eq!(processor_from_midr(0x531f_0010), Some("exynos-m3"));
+    assert_eq!(processor_from_midr(0x534f_0020), Some("exynos-m3"));
+    assert_eq!(processor_from_midr(0x532f_0030), Some("exynos-m3"));
+}
+
+#[test]
+fn midrs_from_fixtures() {
+    let midrs = midrs_from_cpuinfo(fixture!("aarch64-neoverse-n1"));
+    assert_eq!(midrs, [0x413f_d0c1, 0x413f_d0c1]);
+    assert_eq!(processor_from_midr(midrs[0]), Some("neoverse-n1"));
+
+    let midrs = midrs_from_cpuinfo(fixture!("armv7-raspberry-pi-3"));
+    assert_eq!(midrs[0], 0x410f_d034);
+    assert_eq!(processor_from_midr(midrs[0]), Some("cortex-a53"));
+
+    assert!(midrs_from_cpuinfo(fixture!("x86_64-xeon-vm")).is_empty());
+}
+
+#[test]
+fn riscv_isa_from_fixture() {
+    let cpuinfo = fixture!("riscv64-visionfive-2");
+    let isa = RiscvIsa::from_cpuinfo(cpuinfo).unwrap();
+
+    assert_eq!(isa.xlen, 64);
+    assert_eq!(
+        isa.to_string(),
+        "rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb"
+    );
+    assert!(isa.contains("zba"));
+    assert!(!isa.contains("zbs"));
+    assert_eq!(isa.profile(), Some(RiscvProfile::Rva20));
+    assert_eq!(riscv_processor_from_cpuinfo(cpuinfo), Some("sifive-u74"));
+}
+
+#[test]
+fn riscv_isa_strings() {
+    let isa = RiscvIsa::parse("rv64imafdc_zicsr_zifencei_zba_zbb").unwrap();
+    assert_eq!(
+        isa.extensions,
+        [
+            "i", "m", "a", "f", "d", "c", "zicsr", "zifencei", "zba", "zbb"
+        ]
+    );
+    assert_eq!(isa.profile(), Some(RiscvProfile::Rva20));
+
+    let isa = RiscvIsa::parse("RV64I2p1M2p0A2p1F2p2D2p2C2p0_Zicsr2p0").unwrap();
+    assert_eq!(isa.to_string(), "rv64imafdc_zicsr");
+
+    // `g` expands to `imafd_zicsr_zifencei`, and extensions are put in canonical order
+    let isa = RiscvIsa::parse("rv64gc_zbb_zba").unwrap();
+    assert_eq!(isa.to_string(), "rv64imafdc_zicsr_zifencei_zba_zbb");
+
+    assert_eq!(RiscvIsa::parse("x86_64"), None);
+}
+
+#[test]
+fn riscv_profiles() {
+    const RVA22: &str = "rv64gc_zba_zbb_zbs_zicbom_zicboz_zihintpause_zfhmin_zkt";
+    const RVA23: &str = "_zvfhmin_zvbb_zvkt_zihintntl_zicond_zimop_zcmop_zcb_zfa_zawrs";
+
+    let profile = |isa: &str| RiscvIsa::parse(isa).unwrap().profile();
+
+    assert_eq!(profile("rv64imac"), None);
+    assert_eq!(profile("rv64gc"), Some(RiscvProfile::Rva20));
+    assert_eq!(profile(RVA22), Some(RiscvProfile::Rva22));
+    // Each extension of the profile is required
+    assert_eq!(
+        profile(RVA22.strip_suffix("_zkt").unwrap()),
+        Some(RiscvProfile::Rva20)
+    );
+    assert_eq!(
+        profile(&RVA22.replacen("gc", "gcv", 1).replace("_zicbom", "")),
+        Some(RiscvProfile::Rva20)
+    );
+    assert_eq!(
+        profile(&(RVA22.replacen("gc", "gcv", 1) + RVA23)),
+        Some(RiscvProfile::Rva23)
+    );
+    // RVA23 requires the vector extension
+    assert_eq!(
+        profile(&(RVA22.to_string() + RVA23)),
+        Some(RiscvProfile::Rva22)
+    );
+    // Only RV64 profiles are known
+    assert_eq!(profile("rv32gc"), None);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn riscv_hwprobe() {
+    const IMA: u64 = 1 << 0;
+    const FD: u64 = 1 << 0;
+    const C: u64 = 1 << 1;
+    const V: u64 = 1 << 2;
+    const ZBA: u64 = 1 << 3;
+    const ZBB: u64 = 1 << 4;
+    const ZBS: u64 = 1 << 5;
+    const ZICBOZ: u64 = 1 << 6;
+    const ZKT: u64 = 1 << 16;
+    const ZFHMIN: u64 = 1 << 28;
+    const ZIHINTPAUSE: u64 = 1 << 36;
+    const ZICBOM: u64 = 1 << 55;
+
+    let isa = RiscvIsa::from_hwprobe(IMA, FD | C | ZBA | ZBB);
+    assert_eq!(isa.to_string(), "rv64imafdc_zba_zbb");
+    assert_eq!(isa.profile(), Some(RiscvProfile::Rva20));
+
+    let rva22 = FD | C | ZBA | ZBB | ZBS | ZICBOZ | ZKT | ZFHMIN | ZIHINTPAUSE | ZICBOM;
+    let isa = RiscvIsa::from_hwprobe(IMA, rva22 | V);
+    assert!(isa.contains("v"));
+    assert!(isa.contains("zicbom"));
+    assert_eq!(isa.profile(), Some(RiscvProfile::Rva22));
+
+    // Without the IMA base behavior, not even RVA20 is supported
+    let isa = RiscvIsa::from_hwprobe(0, rva22);
+    assert!(!isa.contains("i"));
+    assert_eq!(isa.profile(), None);
+}
+
+// `AT_HWCAP` of ppc64le POWER8 and later: ppc32, ppc64, altivec, fpu, mmu, smt, ic_snoop, dfp, arch_2_06, vsx, archpmu, and true_le
+const PPC64LE_HWCAP: u64 = 0xdc00_65c2;
+const POWER8_HWCAP2: u64 = 0xff00_0000;
+const POWER9_HWCAP2: u64 = 0xbef0_0000;
+const POWER10_HWCAP2: u64 = 0xbef6_0000;
+
+/// The start of the ELF header of `ld64.so.2` on ppc64le
+fn ppc64le_loader() -> [u8; 64] {
+    let mut header = [0; 64];
+    header[..8].copy_from_slice(b"\x7fELF\x02\x01\x01\x00");
+    header[16] = 3; // ET_DYN
+    header[18] = 21; // EM_PPC64
+    header[48] = 2; // ELFv2
+    header
+}
+
+#[test]
+fn power_from_auxv() {
+    let loader = ppc64le_loader();
+
+    let power8 = PowerInfo::from_auxv("power8", None, PPC64LE_HWCAP, POWER8_HWCAP2, Some(&loader));
+    assert_eq!(power8.cpu, Some("pwr8"));
+    assert_eq!(power8.isa, Some((2, 7)));
+    assert_eq!(power8.endianness, Endianness::Little);
+    assert_eq!(power8.abi, Some(PowerAbi::ElfV2));
+    assert_eq!(power8.hardware_platform("ppc64le"), "ppc64le-isa2.7");
+
+    let power9 = PowerInfo::from_auxv("power9", None, PPC64LE_HWCAP, POWER9_HWCAP2, Some(&loader));
+    assert_eq!(power9.cpu, Some("pwr9"));
+    assert_eq!(power9.isa, Some((3, 0)));
+    assert_eq!(power9.hardware_platform("ppc64le"), "ppc64le-isa3.0");
+
+    let power10 = PowerInfo::from_auxv(
+        "power10",
+        Some("power10"),
+        PPC64LE_HWCAP,
+        POWER10_HWCAP2,
+        Some(&loader),
+    );
+    assert_eq!(power10.cpu, Some("pwr10"));
+    assert_eq!(power10.isa, Some((3, 1)));
+    assert_eq!(power10.hardware_platform("ppc64le"), "ppc64le-isa3.1");
+
+    // A POWER10 running a guest in POWER9 compatibility mode
+    let compat = PowerInfo::from_auxv(
+        "power9",
+        Some("power10"),
+        PPC64LE_HWCAP,
+        POWER9_HWCAP2,
+        Some(&loader),
+    );
+    assert_eq!(compat.cpu, Some("pwr9"));
+    assert_eq!(compat.base_platform.as_deref(), Some("power10"));
+}
+
+#[test]
+fn power_abi() {
+    // ppc64 big endian, with the ELFv1 flag left unset
+    let mut loader = ppc64le_loader();
+    loader[5] = 2;
+    loader[18] = 0;
+    loader[19] = 21;
+    loader[48] = 0;
+
+    let info = PowerInfo::from_auxv("power8", None, 0xdc00_65c0, POWER8_HWCAP2, Some(&loader));
+    assert_eq!(info.endianness, Endianness::Big);
+    assert_eq!(info.abi, Some(PowerAbi::ElfV1));
+    assert_eq!(info.hardware_platform("ppc64"), "ppc64-isa2.7");
+
+    // Unknown platforms and ISA versions
+    let info = PowerInfo::from_auxv("ppc-cell-be", None, 0, 0, None);
+    assert_eq!(info.cpu, None);
+    assert_eq!(info.isa, None);
+    assert_eq!(info.abi, None);
+    assert_eq!(info.hardware_platform("ppc64"), "ppc64");
+}
+
+const HWCAP_S390_VXRS: u64 = 1 << 11;
+const HWCAP_S390_VXRS_EXT: u64 = 1 << 13;
+const HWCAP_S390_VXRS_EXT2: u64 = 1 << 15;
+const HWCAP_S390_NNPA: u64 = 1 << 20;
+const HWCAP_S390_ALL_VECTOR: u64 =
+    HWCAP_S390_VXRS | HWCAP_S390_VXRS_EXT | HWCAP_S390_VXRS_EXT2 | HWCAP_S390_NNPA;
+
+#[test]
+fn s390x_machine_types() {
+    let machines = [
+        (2064, 5, "z900"),
+        (2066, 5, "z900"),
+        (2084, 6, "z990"),
+        (2086, 6, "z990"),
+        (2094, 7, "z9-ec"),
+        (2096, 7, "z9-ec"),
+        (2097, 8, "z10"),
+        (2098, 8, "z10"),
+        (2817, 9, "z196"),
+        (2818, 9, "z196"),
+        (2827, 10, "zEC12"),
+        (2828, 10, "zEC12"),
+        (2964, 11, "z13"),
+        (2965, 11, "z13"),
+        (3906, 12, "z14"),
+        (3907, 12, "z14"),
+        (8561, 13, "z15"),
+        (8562, 13, "z15"),
+        (3931, 14, "z16"),
+        (3932, 14, "z16"),
+        (9175, 15, "z17"),
+    ];
+
+    for (machine_type, level, name) in machines {
+        assert_eq!(
+            S390xInfo::machine_arch_level(machine_type),
+            Some(level),
+            "{machine_type}"
+        );
+
+        let info = S390xInfo::from_cpuinfo(
+            &format!("machine         : {machine_type}\n"),
+            HWCAP_S390_ALL_VECTOR,
+        );
+        assert_eq!(info.machine_type, Some(machine_type));
+        assert_eq!(info.arch_level, Some(level), "{machine_type}");
+        assert_eq!(info.processor(), Some(name), "{machine_type}");
+    }
+
+    assert_eq!(S390xInfo::machine_arch_level(1234), None);
+}
+
+#[test]
+fn s390x_from_fixture() {
+    let cpuinfo = fixture!("s390x-z16");
+
+    let info = S390xInfo::from_cpuinfo(cpuinfo, HWCAP_S390_ALL_VECTOR);
+    assert_eq!(info.machine_type, Some(3931));
+    assert_eq!(info.arch_level, Some(14));
+    assert_eq!(info.processor(), Some("z16"));
+
+    // The level is capped by the vector facilities the kernel enables
+    let info = S390xInfo::from_cpuinfo(cpuinfo, HWCAP_S390_VXRS | HWCAP_S390_VXRS_EXT2);
+    assert_eq!(info.arch_level, Some(13));
+    assert_eq!(info.processor(), Some("z15"));
+
+    let info = S390xInfo::from_cpuinfo(cpuinfo, 0);
+    assert_eq!(info.arch_level, Some(10));
+    assert_eq!(info.processor(), Some("zEC12"));
+
+    // Only the `machine` field names the machine type
+    let cpuinfo = format!("vm machine      : 2\n{cpuinfo}");
+    let info = S390xInfo::from_cpuinfo(&cpuinfo, HWCAP_S390_ALL_VECTOR);
+    assert_eq!(info.machine_type, Some(3931));
+
+    let info = S390xInfo::from_cpuinfo(
+        "processor 0: version = 00,  identification = 0C5F28,  machine = 8561\n",
+        HWCAP_S390_ALL_VECTOR,
+    );
+    assert_eq!(info.machine_type, Some(8561));
+
+    let info = S390xInfo::from_cpuinfo(
+        "cpu number      : 0\nmachine         : 3906\n",
+        HWCAP_S390_ALL_VECTOR,
+    );
+    assert_eq!(info.machine_type, Some(3906));
+
+    // Without a machine type, only the facilities are known
+    let info = S390xInfo::from_cpuinfo("", HWCAP_S390_VXRS);
+    assert_eq!(info.machine_type, None);
+    assert_eq!(info.arch_level, Some(11));
+    assert_eq!(info.processor(), Some("z13"));
+}
+
+// `CPUCFG` words 0 to 3, with only the bits that are decoded set
+const LOONGSON_3A5000_CPUCFG: [u32; 4] = [
+    0x0014_c011, // PRID of the LA464 series
+    0x0010_0002, // LA64, UAL
+    0x001c_04c6, // F, D, LSX, LASX, LVZ, LBT
+    0x0000_0000,
+];
+const LOONGSON_3A6000_CPUCFG: [u32; 4] = [
+    0x0014_d000, // PRID of the LA664 series
+    0x0010_0002, // LA64, UAL
+    0x5e1c_04c6, // As 3A5000, and FRECIPE, DIV32, LAM_BH, LAMCAS, SCQ
+    0x0080_0000, // LD_SEQ_SA
+];
+
+#[test]
+fn loongarch_cpucfg() {
+    let info = LoongArchInfo::from_cpucfg(LOONGSON_3A5000_CPUCFG);
+    assert_eq!(info.prid, Some(0x0014_c011));
+    assert_eq!(info.grlen, 64);
+    assert_eq!(info.processor(), Some("la464"));
+    assert_eq!(info.architecture(), Some("la64v1.0"));
+    assert_eq!(
+        info.features.to_target_feature_string(),
+        "+d,+f,+lasx,+lbt,+lsx,+lvz,+ual"
+    );
+
+    let info = LoongArchInfo::from_cpucfg(LOONGSON_3A6000_CPUCFG);
+    assert_eq!(info.processor(), Some("la664"));
+    assert_eq!(info.architecture(), Some("la64v1.1"));
+    assert!(info.features.contains(
+        LoongArchFeatures::FRECIPE
+            | LoongArchFeatures::DIV32
+            | LoongArchFeatures::LAM_BH
+            | LoongArchFeatures::LAMCAS
+            | LoongArchFeatures::SCQ
+            | LoongArchFeatures::LD_SEQ_SA
+    ));
+
+    // Every LA64 v1.1 extension is required
+    let [prid, cfg1, cfg2, cfg3] = LOONGSON_3A6000_CPUCFG;
+    let info = LoongArchInfo::from_cpucfg([prid, cfg1, cfg2 & !(1 << 30), cfg3]);
+    assert_eq!(info.architecture(), Some("la64v1.0"));
+
+    // LA32
+    let info = LoongArchInfo::from_cpucfg([prid, 0x0010_0001, cfg2, cfg3]);
+    assert_eq!(info.grlen, 32);
+    assert_eq!(info.architecture(), None);
+
+    // Another company's processor
+    let info = LoongArchInfo::from_cpucfg([0x0000_c011, cfg1, cfg2, cfg3]);
+    assert_eq!(info.processor(), None);
+}
+
+#[test]
+fn loongarch_from_fixture() {
+    let info = LoongArchInfo::from_cpuinfo(fixture!("loongarch64-3a5000"));
+    assert_eq!(info.prid, Some(0x0014_c011));
+    assert_eq!(info.grlen, 64);
+    assert_eq!(info.processor(), Some("la464"));
+    assert_eq!(info.architecture(), Some("la64v1.0"));
+    // Linux reports LBT as `lbt_x86`, `lbt_arm` and `lbt_mips`, which are not decoded
+    assert_eq!(
+        info.features.to_target_feature_string(),
+        "+d,+f,+lasx,+lsx,+lvz,+ual"
+    );
+}
+
+#[test]
+fn mips_from_fixture() {
+    let info = MipsInfo::from_cpuinfo(fixture!("mips-octeon3")).unwrap();
+    assert_eq!(info.model, "Cavium Octeon III V0.2  FPU V0.0");
+    assert_eq!(info.cpu, Some("octeon3"));
+    assert_eq!(info.isa, Some("mips64r2"));
+    assert_eq!(info.ases, ["vz"]);
+
+    assert_eq!(MipsInfo::from_cpuinfo("processor\t\t: 0\n"), None);
+}
+
+#[test]
+fn mips_models() {
+    let cpu = |model: &str| {
+        MipsInfo::from_cpuinfo(&format!("cpu model\t\t: {model}\n"))
+            .unwrap()
+            .cpu
+    };
+
+    let models = [
+        ("MIPS 4Kc V0.9", "4kc"),
+        ("MIPS 4Km V0.9", "4km"),
+        ("MIPS 4Kp V0.9", "4kp"),
+        ("MIPS 4KSc V0.9", "4ksc"),
+        ("MIPS 4KEc V6.8", "4kec"),
+        ("MIPS 4KEm V6.8", "4kem"),
+        ("MIPS 4KEp V6.8", "4kep"),
+        ("MIPS 4KSd V0.0", "4ksd"),
+        ("MIPS 5Kc V1.0", "5kc"),
+        ("MIPS 5Kf V1.0", "5kf"),
+        ("MIPS 20Kc V1.0", "20kc"),
+        ("MIPS 24Kc V7.4", "24kc"),
+        ("MIPS 24KEc V5.0  FPU V0.0", "24kec"),
+        ("MIPS 34Kc V5.8", "34kc"),
+        ("MIPS 34Kn V5.8", "34kn"),
+        ("MIPS 74Kc V4.12", "74kc"),
+        ("MIPS 1004Kc V2.12", "1004kc"),
+        ("MIPS interAptiv (multi) V2.0  FPU V2.0", "interaptiv"),
+        ("MIPS M4K V0.0", "m4k"),
+        ("MIPS M14K V0.0", "m14k"),
+        ("MIPS M14Kc V0.0", "m14kc"),
+        ("MIPS M14KE V0.0", "m14ke"),
+        ("MIPS M14KEc V0.0", "m14kec"),
+        ("MIPS M5100 V0.0", "m5100"),
+        ("MIPS M5101 V0.0", "m5101"),
+        ("MIPS P5600 V0.0  FPU V0.0", "p5600"),
+        ("MIPS I6400 V0.0  FPU V2.0", "i6400"),
+        ("MIPS I6500 V0.0  FPU V2.0", "i6500"),
+        ("MIPS P6600 V0.0  FPU V2.0", "p6600"),
+        // Each generation of Octeon is checked before the ones it extends
+        ("Cavium Octeon III V0.2  FPU V0.0", "octeon3"),
+        ("Cavium Octeon II V0.1", "octeon2"),
+        ("Cavium Octeon+ V0.1", "octeon+"),
+        ("Cavium Octeon V0.1", "octeon"),
+        ("ICT Loongson-2 V0.2  FPU V0.1", "loongson2e"),
+        ("ICT Loongson-2 V0.3  FPU V0.1", "loongson2f"),
+        ("ICT Loongson-3 V0.5  FPU V0.1", "loongson3a"),
+        ("Loongson-2E V0.2  FPU V0.1", "loongson2e"),
+        ("Loongson-2F V0.3  FPU V0.1", "loongson2f"),
+        (
+            "Loongson-3A R1 (Loongson-3A1000) V0.5  FPU V0.1",
+            "loongson3a",
+        ),
+        ("Loongson-3A R2 (Loongson-3A2000) V0.0  FPU V0.1", "gs464e"),
+        ("Loongson-3A R3 (Loongson-3A3000) V0.0  FPU V0.1", "gs464e"),
+        ("Loongson-3B R2 (Loongson-3B2000) V0.0  FPU V0.1", "gs464e"),
+        (
+            "Loongson-3A R4 (Loongson-3A4000) V0.0  FPU V0.1",
+            "loongson3a",
+        ),
+    ];
+
+    for (model, name) in models {
+        assert_eq!(cpu(model), Some(name), "{model}");
+    }
+
+    assert_eq!(cpu("MIPS 24Kf V7.4"), None);
+    assert_eq!(cpu("Broadcom BMIPS5000 V1.1  FPU V0.1"), None);
+}
+
+#[test]
+fn sparc_from_fixture() {
+    let info = SparcInfo::from_cpuinfo(fixture!("sparc64-t4")).unwrap();
+    assert_eq!(info.model, "UltraSparc T4 (Niagara4)");
+    assert_eq!(info.cpu, Some("niagara4"));
+    assert_eq!(info.machine_type.as_deref(), Some("sun4v"));
+
+    assert_eq!(SparcInfo::from_cpuinfo("type\t\t: sun4v\n"), None);
+}
+
+#[test]
+fn sparc_models() {
+    let cpu = |model: &str, machine_type: &str| {
+        SparcInfo::from_cpuinfo(&format!("cpu\t\t: {model}\ntype\t\t: {machine_type}\n"))
+            .unwrap()
+            .cpu
+    };
+
+    let models = [
+        // The numbered generations of Niagara are checked before the first
+        ("UltraSparc T2 (Niagara2)", "niagara2"),
+        ("UltraSparc T3 (Niagara3)", "niagara3"),
+        ("UltraSparc T4 (Niagara4)", "niagara4"),
+        ("UltraSparc T5 (Niagara5)", "niagara4"),
+        ("UltraSparc T1 (Niagara)", "niagara"),
+        ("SPARC-M7", "niagara7"),
+        ("SPARC-S7", "niagara7"),
+        ("SPARC-M8", "m8"),
+        ("UltraSparc T1", "niagara"),
+        ("UltraSparc T2", "niagara2"),
+        ("UltraSparc T3", "niagara3"),
+        ("UltraSparc T4", "niagara4"),
+        ("UltraSparc T5", "niagara4"),
+        // UltraSPARC III and IV are checked before the earlier UltraSPARCs
+        ("TI UltraSparc III (Cheetah)", "ultrasparc3"),
+        ("TI UltraSparc IIIi (Jalapeno)", "ultrasparc3"),
+        ("TI UltraSparc IV (Jaguar)", "ultrasparc3"),
+        ("TI UltraSparc IIi (Sabre)", "ultrasparc"),
+        ("Fujitsu SPARC64-VII", "v9"),
+    ];
+
+    for (model, name) in models {
+        assert_eq!(cpu(model, "sun4v"), Some(name), "{model}");
+    }
+
+    // Unknown processors fall back to the architecture of the machine
+    assert_eq!(cpu("Unknown CPU", "sun4u"), Some("v9"));
+    assert_eq!(cpu("Unknown CPU", "sun4v"), Some("v9"));
+    assert_eq!(cpu("TI MicroSparc II", "sun4m"), Some("v8"));
+    assert_eq!(cpu("Unknown CPU", "sun4d"), Some("v8"));
+    assert_eq!(cpu("Unknown CPU", "sun4c"), None);
+}